@@ -1,21 +1,45 @@
 mod def;
+mod order;
+mod iter;
+mod convert;
+mod blend;
+mod gamma;
+mod dither;
+mod palette;
 mod buf;
+#[cfg(any(feature = "alloc", test))]
+mod vec;
 mod bw;
 mod raw;
 mod gs;
 mod gsa;
 mod rgb;
 mod rgba;
+mod hsv;
+mod yuv;
 mod idx;
+mod dynamic;
 
 pub use self::def::*;
+pub use self::order::*;
+pub use self::iter::*;
+pub use self::convert::*;
+pub use self::blend::*;
+pub use self::gamma::*;
+pub use self::dither::*;
+pub use self::palette::*;
 pub use self::buf::*;
+#[cfg(any(feature = "alloc", test))]
+pub use self::vec::*;
 
 pub use self::bw::BW;
 pub use self::gs::GS;
 pub use self::gsa::GSA;
 pub use self::rgb::RGB;
 pub use self::rgba::RGBA;
+pub use self::hsv::{HSV, HSL};
+pub use self::yuv::YCbCr;
+pub use self::dynamic::DynFormat;
 
 pub mod format {
     pub use super::bw::*;
@@ -25,4 +49,5 @@ pub mod format {
     pub use super::rgb::*;
     pub use super::rgba::*;
     pub use super::idx::*;
+    pub use super::yuv::{YUYV, UYVY, NV12};
 }
@@ -0,0 +1,207 @@
+use crate::ConstDefault;
+use super::{BW, GS, GSA, RGB, RGBA, srgb_to_linear, linear_to_srgb};
+
+/// Multiply two values which represents fractions of 255 with rounding
+///
+/// Uses only integer operations without division.
+pub fn mul_div255(a: u8, b: u8) -> u8 {
+    let t = a as u16 * b as u16 + 128;
+    ((t + (t >> 8)) >> 8) as u8
+}
+
+/// Interpolate value between `a` and `b` using `t` as fraction of 255
+pub fn lerp(a: u8, b: u8, t: u8) -> u8 {
+    let t = a as u32 * (255 - t) as u32 + b as u32 * t as u32 + 128;
+    ((t + (t >> 8)) >> 8) as u8
+}
+
+/// Blending operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlendOp {
+    /// Porter-Duff source over destination
+    SrcOver,
+    /// Multiply source by destination (darkens)
+    Multiply,
+    /// Inverse multiply of inverted source and destination (lightens)
+    Screen,
+    /// Add source to destination with saturation
+    Add,
+}
+
+impl ConstDefault for BlendOp {
+    const DEFAULT: Self = BlendOp::SrcOver;
+}
+
+impl Default for BlendOp {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl BlendOp {
+    /// Blend single channel of source with alpha over destination
+    pub fn apply(&self, dst: u8, src: u8, alpha: u8) -> u8 {
+        let mixed = match self {
+            BlendOp::SrcOver => src,
+            BlendOp::Multiply => mul_div255(src, dst),
+            BlendOp::Screen => (src as u16 + dst as u16 - mul_div255(src, dst) as u16) as u8,
+            BlendOp::Add => src.saturating_add(dst),
+        };
+
+        lerp(dst, mixed, alpha)
+    }
+
+    /// Blend single channel of sRGB encoded source with alpha over destination in linear light
+    pub fn apply_srgb(&self, dst: u8, src: u8, alpha: u8) -> u8 {
+        let d = srgb_to_linear(dst) as u32;
+        let s = srgb_to_linear(src) as u32;
+
+        let mixed = match self {
+            BlendOp::SrcOver => s,
+            BlendOp::Multiply => (s * d + 32767) / 65535,
+            BlendOp::Screen => s + d - (s * d + 32767) / 65535,
+            BlendOp::Add => (s + d).min(65535),
+        };
+
+        let alpha = alpha as u32;
+        linear_to_srgb(((d * (255 - alpha) + mixed * alpha + 127) / 255) as u16)
+    }
+}
+
+/// Blending of source color over opaque color
+pub trait Blend<Src> {
+    /// Blend source color over this color using operator and global opacity
+    fn blend(self, src: Src, op: BlendOp, opacity: u8) -> Self;
+
+    /// Blend source color over this color in linear light assuming sRGB encoding
+    ///
+    /// This gives better results for anti-aliasing but requires table lookups.
+    fn blend_srgb(self, src: Src, op: BlendOp, opacity: u8) -> Self;
+}
+
+impl<Src> Blend<Src> for RGB
+where
+    Src: Into<RGBA>,
+{
+    fn blend(self, src: Src, op: BlendOp, opacity: u8) -> Self {
+        self.blend_with(src.into(), opacity, |dst, src, alpha| op.apply(dst, src, alpha))
+    }
+
+    fn blend_srgb(self, src: Src, op: BlendOp, opacity: u8) -> Self {
+        self.blend_with(src.into(), opacity, |dst, src, alpha| op.apply_srgb(dst, src, alpha))
+    }
+}
+
+impl RGB {
+    fn blend_with<F: Fn(u8, u8, u8) -> u8>(self, src: RGBA, opacity: u8, apply: F) -> Self {
+        let alpha = mul_div255(src.a, opacity);
+
+        if alpha == 0 {
+            return self;
+        }
+
+        RGB::new(apply(self.r, src.r, alpha), apply(self.g, src.g, alpha), apply(self.b, src.b, alpha))
+    }
+}
+
+impl<Src> Blend<Src> for GS
+where
+    Src: Into<GSA>,
+{
+    fn blend(self, src: Src, op: BlendOp, opacity: u8) -> Self {
+        self.blend_with(src.into(), opacity, |dst, src, alpha| op.apply(dst, src, alpha))
+    }
+
+    fn blend_srgb(self, src: Src, op: BlendOp, opacity: u8) -> Self {
+        self.blend_with(src.into(), opacity, |dst, src, alpha| op.apply_srgb(dst, src, alpha))
+    }
+}
+
+impl GS {
+    fn blend_with<F: Fn(u8, u8, u8) -> u8>(self, src: GSA, opacity: u8, apply: F) -> Self {
+        let alpha = mul_div255(src.a, opacity);
+
+        if alpha == 0 {
+            return self;
+        }
+
+        GS::new(apply(self.v, src.v, alpha))
+    }
+}
+
+impl<Src> Blend<Src> for BW
+where
+    Src: Into<GSA>,
+{
+    fn blend(self, src: Src, op: BlendOp, opacity: u8) -> Self {
+        GS::from(self).blend(src, op, opacity).into()
+    }
+
+    fn blend_srgb(self, src: Src, op: BlendOp, opacity: u8) -> Self {
+        GS::from(self).blend_srgb(src, op, opacity).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn mul() {
+        for a in 0..=255u16 {
+            for b in 0..=255u16 {
+                let exact = ((a * b) as f32 / 255.0).round() as u8;
+                assert_eq!(mul_div255(a as u8, b as u8), exact);
+            }
+        }
+    }
+
+    #[test]
+    fn interpolate() {
+        assert_eq!(lerp(0, 255, 0), 0);
+        assert_eq!(lerp(0, 255, 255), 255);
+        assert_eq!(lerp(10, 200, 255), 200);
+        assert_eq!(lerp(0, 255, 128), 128);
+        assert_eq!(lerp(200, 100, 51), 180);
+    }
+
+    #[test]
+    fn src_over() {
+        let dst = RGB::new(0, 100, 200);
+
+        assert_eq!(dst.blend(RGBA::new(255, 255, 255, 0), BlendOp::SrcOver, 255), dst);
+        assert_eq!(dst.blend(RGBA::new(255, 0, 0, 255), BlendOp::SrcOver, 255), RGB::new(255, 0, 0));
+        assert_eq!(dst.blend(RGBA::new(255, 0, 0, 255), BlendOp::SrcOver, 0), dst);
+        assert_eq!(dst.blend(RGBA::new(255, 0, 0, 255), BlendOp::SrcOver, 51), RGB::new(51, 80, 160));
+        assert_eq!(dst.blend(RGB::new(1, 2, 3), BlendOp::SrcOver, 255), RGB::new(1, 2, 3));
+    }
+
+    #[test]
+    fn ops() {
+        let dst = RGB::new(128, 255, 0);
+        let src = RGBA::new(128, 128, 128, 255);
+
+        assert_eq!(dst.blend(src, BlendOp::Multiply, 255), RGB::new(64, 128, 0));
+        assert_eq!(dst.blend(src, BlendOp::Screen, 255), RGB::new(192, 255, 128));
+        assert_eq!(dst.blend(src, BlendOp::Add, 255), RGB::new(255, 255, 128));
+    }
+
+    #[test]
+    fn gray() {
+        assert_eq!(GS::new(100).blend(GSA::new(200, 128), BlendOp::SrcOver, 255), GS::new(150));
+        assert_eq!(GS::new(0).blend(RGBA::new(0, 255, 0, 255), BlendOp::SrcOver, 255), GS::new(149));
+        assert_eq!(BW::from(false).blend(GSA::new(255, 200), BlendOp::SrcOver, 255), true.into());
+        assert_eq!(BW::from(false).blend(GSA::new(255, 200), BlendOp::SrcOver, 100), false.into());
+    }
+
+    #[test]
+    fn srgb() {
+        let dst = RGB::new(0, 255, 100);
+
+        assert_eq!(dst.blend_srgb(RGBA::new(255, 0, 100, 128), BlendOp::SrcOver, 255), RGB::new(188, 187, 100));
+        assert_eq!(dst.blend_srgb(RGBA::new(255, 0, 100, 255), BlendOp::SrcOver, 255), RGB::new(255, 0, 100));
+        assert_eq!(dst.blend_srgb(RGBA::new(255, 0, 100, 255), BlendOp::SrcOver, 0), dst);
+        assert_eq!(dst.blend_srgb(RGBA::new(255, 255, 255, 255), BlendOp::Multiply, 255), dst);
+        assert_eq!(GS::new(0).blend_srgb(GSA::new(255, 128), BlendOp::SrcOver, 255), GS::new(188));
+    }
+}
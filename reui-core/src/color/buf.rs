@@ -1,61 +1,131 @@
-use core::ops::{Add, Mul, Div};
+use core::ops::{Add, Mul, Div, Range};
+use core::mem::{MaybeUninit, size_of};
 use typenum::{U7, U8, Sum, Prod, Quot};
 use generic_array::{GenericArray, ArrayLength};
-use super::{ColorFmt, ColorGet, ColorSet, ColorBuf, ColorBufMut};
+use crate::ConstDefault;
+use super::{ColorFmt, ColorGet, ColorSet, ColorBuf, ColorBufMut, ColorsIter};
 
-/// Static-sized buffer for colors
-pub struct ColorArray<Len, Fmt>
+/// Number of colors in static-sized buffer
+///
+/// Implemented for typenum unsigned integers to get size of buffer in bytes for format.
+///
+/// The byte length cannot be computed from const generics on stable Rust yet,
+/// so errors about unsatisfied bounds still refers to typenum types.
+pub trait ColorLen<Fmt: ColorFmt> {
+    /// Number of bytes to store colors
+    type Bytes: ArrayLength<u8>;
+}
+
+impl<Len, Fmt> ColorLen<Fmt> for Len
 where
     Fmt: ColorFmt,
     Len: Mul<Fmt::ColorBits>,
     Prod<Len, Fmt::ColorBits>: Add<U7>,
     Sum<Prod<Len, Fmt::ColorBits>, U7>: Div<U8>,
     Quot<Sum<Prod<Len, Fmt::ColorBits>, U7>, U8>: ArrayLength<u8>,
+{
+    type Bytes = Quot<Sum<Prod<Len, Fmt::ColorBits>, U7>, U8>;
+}
+
+/// Static-sized buffer for colors
+///
+/// The number of colors is typenum unsigned integer (i.e. `typenum::U<16>`).
+/// Use [`PixelArray`](crate::PixelArray) to give the size as plain integers.
+pub struct ColorArray<Len, Fmt>
+where
+    Fmt: ColorFmt,
+    Len: ColorLen<Fmt>,
 {
     format: Fmt,
-    data: GenericArray<u8, Quot<Sum<Prod<Len, Fmt::ColorBits>, U7>, U8>>,
+    data: GenericArray<u8, Len::Bytes>,
 }
 
 impl<Len, Fmt> ColorArray<Len, Fmt>
 where
     Fmt: ColorFmt,
-    Len: Mul<Fmt::ColorBits>,
-    Prod<Len, Fmt::ColorBits>: Add<U7>,
-    Sum<Prod<Len, Fmt::ColorBits>, U7>: Div<U8>,
-    Quot<Sum<Prod<Len, Fmt::ColorBits>, U7>, U8>: ArrayLength<u8>,
+    Len: ColorLen<Fmt>,
 {
-    /// Create buffer without initializing
-    pub fn new() -> Self
+    /// Create buffer filled by zero bytes
+    pub const fn new() -> Self
     where
-        Fmt: Default,
+        Fmt: ConstDefault,
     {
-        Self::new_with_format(Fmt::default())
+        Self::new_with_format(Fmt::DEFAULT)
     }
 
-    /// Create buffer without initializing using specified format
-    pub fn new_with_format(format: Fmt) -> Self {
-        let data = unsafe { core::mem::MaybeUninit::uninit().assume_init() };
+    /// Create buffer filled by zero bytes using specified format
+    pub const fn new_with_format(format: Fmt) -> Self {
+        // zero bytes is valid value of byte array
+        let data = unsafe { MaybeUninit::zeroed().assume_init() };
         Self { format, data }
     }
+
+    /// Create buffer filled by color
+    pub fn new_filled(color: Fmt::ColorType) -> Self
+    where
+        Fmt: ColorSet + ConstDefault,
+        Fmt::ColorType: Clone,
+    {
+        Self::new_filled_with_format(Fmt::DEFAULT, color)
+    }
+
+    /// Create buffer filled by color using specified format
+    pub fn new_filled_with_format(format: Fmt, color: Fmt::ColorType) -> Self
+    where
+        Fmt: ColorSet,
+        Fmt::ColorType: Clone,
+    {
+        let mut buffer = Self::new_with_format(format);
+        let data = buffer.data.as_mut();
+        buffer.format.fill_colors(data, 0..buffer.format.num_colors(data), color);
+        buffer
+    }
+
+    /// Get uninitialized data for buffer
+    ///
+    /// The data can be filled using [`ColorArray::as_uninit_mut`]
+    /// and turned to buffer using [`ColorArray::from_uninit`].
+    pub const fn uninit() -> MaybeUninit<GenericArray<u8, Len::Bytes>> {
+        MaybeUninit::uninit()
+    }
+
+    /// Get bytes of uninitialized data for writing
+    pub fn as_uninit_mut(data: &mut MaybeUninit<GenericArray<u8, Len::Bytes>>) -> &mut [MaybeUninit<u8>] {
+        let len = size_of::<GenericArray<u8, Len::Bytes>>();
+        // byte array has the same layout as array of uninitialized bytes
+        unsafe { core::slice::from_raw_parts_mut(data.as_mut_ptr() as *mut MaybeUninit<u8>, len) }
+    }
+
+    /// Create buffer from data which was initialized
+    ///
+    /// # Safety
+    ///
+    /// All bytes of data must be written before.
+    pub unsafe fn from_uninit(data: MaybeUninit<GenericArray<u8, Len::Bytes>>) -> Self
+    where
+        Fmt: ConstDefault,
+    {
+        Self::from_uninit_with_format(Fmt::DEFAULT, data)
+    }
+
+    /// Create buffer from data which was initialized using specified format
+    ///
+    /// # Safety
+    ///
+    /// All bytes of data must be written before.
+    pub unsafe fn from_uninit_with_format(format: Fmt, data: MaybeUninit<GenericArray<u8, Len::Bytes>>) -> Self {
+        Self { format, data: data.assume_init() }
+    }
 }
 
 impl<Len, Fmt> Default for ColorArray<Len, Fmt>
 where
     Fmt: ColorSet + Default,
     Fmt::ColorType: Default + Copy,
-    Len: Mul<Fmt::ColorBits>,
-    Prod<Len, Fmt::ColorBits>: Add<U7>,
-    Sum<Prod<Len, Fmt::ColorBits>, U7>: Div<U8>,
-    Quot<Sum<Prod<Len, Fmt::ColorBits>, U7>, U8>: ArrayLength<u8>,
+    Len: ColorLen<Fmt>,
 {
     fn default() -> Self {
-        let mut buffer = Self::new();
-        let data = buffer.data.as_mut();
-        let c = Fmt::ColorType::default();
-        for i in 0..buffer.format.num_colors(data) {
-            buffer.format.set_color(data, i, c);
-        }
-        buffer
+        Self::new_filled_with_format(Fmt::default(), Fmt::ColorType::default())
     }
 }
 
@@ -63,10 +133,7 @@ impl<Len, Fmt> AsRef<[u8]> for ColorArray<Len, Fmt>
 where
     Fmt: ColorSet,
     Fmt::ColorType: Default,
-    Len: Mul<Fmt::ColorBits>,
-    Prod<Len, Fmt::ColorBits>: Add<U7>,
-    Sum<Prod<Len, Fmt::ColorBits>, U7>: Div<U8>,
-    Quot<Sum<Prod<Len, Fmt::ColorBits>, U7>, U8>: ArrayLength<u8>,
+    Len: ColorLen<Fmt>,
 {
     fn as_ref(&self) -> &[u8] {
         self.data.as_ref()
@@ -77,10 +144,7 @@ impl<Len, Fmt> AsMut<[u8]> for ColorArray<Len, Fmt>
 where
     Fmt: ColorSet,
     Fmt::ColorType: Default,
-    Len: Mul<Fmt::ColorBits>,
-    Prod<Len, Fmt::ColorBits>: Add<U7>,
-    Sum<Prod<Len, Fmt::ColorBits>, U7>: Div<U8>,
-    Quot<Sum<Prod<Len, Fmt::ColorBits>, U7>, U8>: ArrayLength<u8>,
+    Len: ColorLen<Fmt>,
 {
     fn as_mut(&mut self) -> &mut [u8] {
         self.data.as_mut()
@@ -91,10 +155,7 @@ impl<Len, Fmt> AsRef<Fmt> for ColorArray<Len, Fmt>
 where
     Fmt: ColorSet,
     Fmt::ColorType: Default,
-    Len: Mul<Fmt::ColorBits>,
-    Prod<Len, Fmt::ColorBits>: Add<U7>,
-    Sum<Prod<Len, Fmt::ColorBits>, U7>: Div<U8>,
-    Quot<Sum<Prod<Len, Fmt::ColorBits>, U7>, U8>: ArrayLength<u8>,
+    Len: ColorLen<Fmt>,
 {
     fn as_ref(&self) -> &Fmt {
         &self.format
@@ -105,10 +166,7 @@ impl<Len, Fmt> ColorBuf for ColorArray<Len, Fmt>
 where
     Fmt: ColorGet,
     Fmt::ColorType: Default,
-    Len: Mul<Fmt::ColorBits>,
-    Prod<Len, Fmt::ColorBits>: Add<U7>,
-    Sum<Prod<Len, Fmt::ColorBits>, U7>: Div<U8>,
-    Quot<Sum<Prod<Len, Fmt::ColorBits>, U7>, U8>: ArrayLength<u8>,
+    Len: ColorLen<Fmt>,
 {
     type ColorFmt = Fmt;
 
@@ -120,19 +178,24 @@ where
         self.format.get_color(self.data.as_ref(), index)
     }
 
-    /*fn gets(&self, range: Range<usize>, length: usize, stride: usize) -> Fmt::ColorIter {
+    fn gets(&self, range: Range<usize>, length: usize, stride: usize) -> ColorsIter<'_, Fmt> {
         self.format.get_colors(self.data.as_ref(), range, length, stride)
-    }*/
+    }
+
+    fn bytes(&self) -> &[u8] {
+        self.data.as_ref()
+    }
+
+    fn color_bits(&self) -> usize {
+        self.format.color_bits()
+    }
 }
 
 impl<Len, Fmt> ColorBufMut for ColorArray<Len, Fmt>
 where
     Fmt: ColorGet + ColorSet,
     Fmt::ColorType: Default,
-    Len: Mul<Fmt::ColorBits>,
-    Prod<Len, Fmt::ColorBits>: Add<U7>,
-    Sum<Prod<Len, Fmt::ColorBits>, U7>: Div<U8>,
-    Quot<Sum<Prod<Len, Fmt::ColorBits>, U7>, U8>: ArrayLength<u8>,
+    Len: ColorLen<Fmt>,
 {
     fn set(&mut self, index: usize, color: Fmt::ColorType)
     where
@@ -140,66 +203,61 @@ where
     {
         self.format.set_color(self.data.as_mut(), index, color)
     }
-}
-
-impl<'a, Len, Fmt> ColorBuf for &'a ColorArray<Len, Fmt>
-where
-    Fmt: ColorGet,
-    Fmt::ColorType: Default,
-    Len: Mul<Fmt::ColorBits>,
-    Prod<Len, Fmt::ColorBits>: Add<U7>,
-    Sum<Prod<Len, Fmt::ColorBits>, U7>: Div<U8>,
-    Quot<Sum<Prod<Len, Fmt::ColorBits>, U7>, U8>: ArrayLength<u8>,
-{
-    type ColorFmt = Fmt;
 
-    fn len(&self) -> usize {
-        self.format.num_colors(self.data.as_ref())
+    fn sets<Colors>(&mut self, range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Fmt::ColorType>,
+    {
+        self.format.set_colors(self.data.as_mut(), range, length, stride, colors)
     }
 
-    fn get(&self, index: usize) -> Fmt::ColorType {
-        self.format.get_color(self.data.as_ref(), index)
+    fn fill(&mut self, range: Range<usize>, color: Fmt::ColorType)
+    where
+        Fmt::ColorType: Clone,
+    {
+        self.format.fill_colors(self.data.as_mut(), range, color)
     }
 
-    /*fn gets(&self, range: Range<usize>, length: usize, stride: usize) -> Fmt::ColorIter {
-        self.format.get_colors(self.data.as_ref(), range, length, stride)
-    }*/
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        self.data.as_mut()
+    }
 }
 
-impl<'a, Len, Fmt> ColorBuf for &'a mut ColorArray<Len, Fmt>
-where
-    Fmt: ColorGet,
-    Fmt::ColorType: Default,
-    Len: Mul<Fmt::ColorBits>,
-    Prod<Len, Fmt::ColorBits>: Add<U7>,
-    Sum<Prod<Len, Fmt::ColorBits>, U7>: Div<U8>,
-    Quot<Sum<Prod<Len, Fmt::ColorBits>, U7>, U8>: ArrayLength<u8>,
-{
-    type ColorFmt = Fmt;
+#[cfg(test)]
+mod test {
+    use super::*;
+    use typenum::U4;
+    use crate::{format, RGB};
 
-    fn len(&self) -> usize {
-        self.format.num_colors(self.data.as_ref())
+    const ARRAY: ColorArray<U4, format::RGB565> = ColorArray::new();
+
+    #[test]
+    fn zeroed() {
+        assert_eq!(ARRAY.bytes(), [0u8; 8]);
+        assert_eq!(ColorArray::<U4, format::GS4>::new().bytes(), [0u8; 2]);
     }
 
-    fn get(&self, index: usize) -> Fmt::ColorType {
-        self.format.get_color(self.data.as_ref(), index)
+    #[test]
+    fn filled() {
+        let array = ColorArray::<U4, format::RGB332>::new_filled(RGB::new(255, 0, 0));
+
+        assert_eq!(array.bytes(), [0b1110_0000; 4]);
+        assert_eq!(array.get(3), RGB::new(252, 0, 0));
     }
 
-    /*fn gets(&self, range: Range<usize>, length: usize, stride: usize) -> Fmt::ColorIter {
-        self.format.get_colors(self.data.as_ref(), range, length, stride)
-    }*/
-}
+    #[test]
+    fn uninit() {
+        let mut data = ColorArray::<U4, format::RGB888>::uninit();
+        let bytes = ColorArray::<U4, format::RGB888>::as_uninit_mut(&mut data);
 
-impl<'a, Len, Fmt> ColorBufMut for &'a mut ColorArray<Len, Fmt>
-where
-    Fmt: ColorGet + ColorSet,
-    Fmt::ColorType: Default,
-    Len: Mul<Fmt::ColorBits>,
-    Prod<Len, Fmt::ColorBits>: Add<U7>,
-    Sum<Prod<Len, Fmt::ColorBits>, U7>: Div<U8>,
-    Quot<Sum<Prod<Len, Fmt::ColorBits>, U7>, U8>: ArrayLength<u8>,
-{
-    fn set(&mut self, index: usize, color: Fmt::ColorType) {
-        self.format.set_color(self.data.as_mut(), index, color)
+        assert_eq!(bytes.len(), 12);
+
+        for byte in bytes {
+            byte.write(0xff);
+        }
+
+        let array = unsafe { ColorArray::<U4, format::RGB888>::from_uninit(data) };
+
+        assert_eq!(array.bytes(), [0xff; 12]);
     }
 }
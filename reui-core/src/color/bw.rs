@@ -1,6 +1,7 @@
-//use core::ops::Range;
+use core::ops::Range;
+use core::marker::PhantomData;
 use crate::{ConstDefault};
-use super::{ColorFmt, ColorGet, ColorSet};
+use super::{ColorFmt, ColorGet, ColorSet, ColorAlpha, BitOrder, LSB, ColorSpans, set_bits};
 
 /// Black and white color
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -15,21 +16,32 @@ impl From<bool> for BW {
     }
 }
 
-impl Into<bool> for BW {
-    fn into(self) -> bool {
-        self.v
+impl From<BW> for bool {
+    fn from(bw: BW) -> Self {
+        bw.v
+    }
+}
+
+impl ColorAlpha for BW {
+    fn alpha(&self) -> u8 {
+        255
     }
 }
 
 /// 1-bit black/white color format
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub struct BW1;
+pub struct BW1<Order = LSB> {
+    _order: PhantomData<Order>,
+}
 
-impl ConstDefault for BW1 {
-    const DEFAULT: Self = Self;
+/// BW1 format with LSB-first bit order
+pub const BW1: BW1 = BW1 { _order: PhantomData };
+
+impl<Order> ConstDefault for BW1<Order> {
+    const DEFAULT: Self = Self { _order: PhantomData };
 }
 
-impl ColorFmt for BW1 {
+impl<Order> ColorFmt for BW1<Order> {
     type ColorType = BW;
     type ColorBits = typenum::U1;
 
@@ -38,47 +50,39 @@ impl ColorFmt for BW1 {
     }
 }
 
-impl ColorGet for BW1 {
-    fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
-        Self::ColorType { v: buffer[index / 8] & (1u8 << (index % 8)) > 0 }
+impl<Order> BW1<Order> {
+    fn unpack(bits: u8) -> BW {
+        BW { v: bits > 0 }
     }
 
-    /*type ColorIter = BW1Iter<'a>;
-
-    fn get_colors(&self, buffer: &[u8], range: Range<usize>, length: usize, stride: usize) -> Self::ColorIter {
-
-    }*/
+    fn pack(color: BW) -> u8 {
+        color.v as u8
+    }
 }
 
-/*pub struct BW1Iter<'b> {
-    buffer: &'b [u8],
-    byte: usize,
-    bit: u8,
+impl<Order: BitOrder> ColorGet for BW1<Order> {
+    fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
+        Self::unpack(Order::get_bits(buffer, index, 1))
+    }
 }
 
-impl<'b> Iterator for BW1Iter<'b> {
-    type Item = BW;
-
-    fn next(&mut self) -> Option<Self::Item> {
-
+impl<Order: BitOrder> ColorSet for BW1<Order> {
+    fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
+        Order::set_bits(buffer, index, 1, Self::pack(color));
     }
-}*/
 
-impl ColorSet for BW1 {
-    fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
-        let cell = &mut buffer[index / 8];
-        let bit = 1u8 << (index % 8);
-        if color.v {
-            *cell |= bit;
-        } else {
-            *cell &= !bit;
-        }
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        set_bits::<typenum::U1, Order, _>(buffer, ColorSpans::new(range, length, stride), colors, Self::pack);
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::MSB;
 
     #[test]
     fn bw1_get() {
@@ -129,4 +133,33 @@ mod test {
         assert_eq!(BW1.get_color(&buf, 10), false.into());
         assert_eq!(&buf, &[2, 1, 0]);
     }
+
+    #[test]
+    fn bw1_msb() {
+        let fmt = BW1::<MSB>::DEFAULT;
+        let mut buf = [0b1000_0000, 0b0100_0000];
+
+        assert_eq!(fmt.get_color(&buf, 0), true.into());
+        assert_eq!(fmt.get_color(&buf, 1), false.into());
+        assert_eq!(fmt.get_color(&buf, 7), false.into());
+        assert_eq!(fmt.get_color(&buf, 9), true.into());
+
+        fmt.set_color(&mut buf, 0, false.into());
+        fmt.set_color(&mut buf, 7, true.into());
+        fmt.set_color(&mut buf, 8, true.into());
+
+        assert_eq!(&buf, &[0b0000_0001, 0b1100_0000]);
+    }
+
+    #[test]
+    fn bw1_colors() {
+        let fmt = BW1::<MSB>::DEFAULT;
+        let mut buf = [0b1010_0000, 0b0000_0001];
+
+        assert!(fmt.get_colors(&buf, 0..16, 2, 5).eq([true, false, false, false, false, false, true].iter().map(|v| BW::from(*v))));
+
+        fmt.set_colors(&mut buf, 3..16, 8, 8, [true, true].iter().map(|v| BW::from(*v)));
+
+        assert_eq!(&buf, &[0b1011_1000, 0b0000_0001]);
+    }
 }
@@ -0,0 +1,68 @@
+use super::RGBA;
+
+/// Color conversion
+///
+/// Any color type which can be converted to and from `RGBA` can be converted to any other
+/// such color type, which is useful in generic code where no direct `From` implementation exists.
+pub trait ColorConvert: Sized {
+    /// Convert color to `RGBA`
+    fn to_rgba(self) -> RGBA;
+
+    /// Convert color from `RGBA`
+    fn from_rgba(rgba: RGBA) -> Self;
+
+    /// Convert color to another color type
+    fn convert<To: ColorConvert>(self) -> To {
+        To::from_rgba(self.to_rgba())
+    }
+}
+
+impl<Color> ColorConvert for Color
+where
+    Color: Into<RGBA> + From<RGBA>,
+{
+    fn to_rgba(self) -> RGBA {
+        self.into()
+    }
+
+    fn from_rgba(rgba: RGBA) -> Self {
+        rgba.into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{BW, GS, GSA, RGB};
+
+    #[test]
+    fn luma() {
+        assert_eq!(RGB::new(255, 255, 255).luma(), 255);
+        assert_eq!(RGB::new(0, 0, 0).luma(), 0);
+        assert_eq!(RGB::new(255, 0, 0).luma(), 76);
+        assert_eq!(RGB::new(0, 255, 0).luma(), 149);
+        assert_eq!(RGB::new(0, 0, 255).luma(), 28);
+        assert_eq!(GS::from(RGB::new(0, 255, 0)), GS::new(149));
+        assert_eq!(GSA::from(RGBA::new(0, 255, 0, 10)), GSA::new(149, 10));
+    }
+
+    #[test]
+    fn bw() {
+        assert_eq!(BW::from(GS::new(127)), false.into());
+        assert_eq!(BW::from(GS::new(128)), true.into());
+        assert_eq!(BW::from(RGB::new(0, 0, 255)), false.into());
+        assert_eq!(BW::from(RGBA::new(255, 255, 0, 0)), true.into());
+        assert_eq!(RGB::from(BW::from(true)), RGB::new(255, 255, 255));
+        assert_eq!(GSA::from(BW::from(false)), GSA::new(0, 255));
+    }
+
+    #[test]
+    fn convert() {
+        assert_eq!(BW::from(true).convert::<RGB>(), RGB::new(255, 255, 255));
+        assert_eq!(GSA::new(10, 20).convert::<RGBA>(), RGBA::new(10, 10, 10, 20));
+        assert_eq!(GSA::new(10, 20).convert::<GSA>(), GSA::new(10, 20));
+        assert_eq!(GS::new(77).convert::<RGB>().convert::<GS>(), GS::new(77));
+        assert_eq!(RGB::new(255, 0, 0).convert::<GS>(), GS::new(76));
+        assert_eq!(RGBA::new(1, 2, 3, 4).convert::<RGB>(), RGB::new(1, 2, 3));
+    }
+}
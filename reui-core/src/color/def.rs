@@ -1,4 +1,6 @@
+use core::ops::Range;
 use typenum::Unsigned;
+use super::{ColorSpans, ColorsIter};
 
 /// Color format definition
 pub trait ColorFmt {
@@ -10,6 +12,19 @@ pub trait ColorFmt {
 
     /// Measures the number of colors which can fit into buffer
     fn num_colors(&self, buffer: &[u8]) -> usize;
+
+    /// The packed size of color in number of bits
+    ///
+    /// Formats which selected at runtime overrides it, so `ColorBits` is the maximum size.
+    fn color_bits(&self) -> usize {
+        Self::ColorBits::USIZE
+    }
+}
+
+/// Color opacity
+pub trait ColorAlpha {
+    /// Get alpha of color (0 is transparent and 255 is opaque)
+    fn alpha(&self) -> u8;
 }
 
 /// Color getter
@@ -17,11 +32,13 @@ pub trait ColorGet: ColorFmt {
     /// Gets color which located at specified index
     fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType;
 
-    //// The type of iterator for colors
-    //type ColorIter: Iterator<Item = Self::ColorType>;
-
-    //// Gets colors at specified range by pieces of specified length with specified stride
-    //fn get_colors(&self, buffer: &[u8], range: Range<usize>, length: usize, stride: usize) -> Self::ColorIter;
+    /// Gets colors at specified range by pieces of specified length with specified stride
+    fn get_colors<'a>(&'a self, buffer: &'a [u8], range: Range<usize>, length: usize, stride: usize) -> ColorsIter<'a, Self>
+    where
+        Self: Sized,
+    {
+        ColorsIter::new(self, buffer, ColorSpans::new(range, length, stride))
+    }
 }
 
 /// Color setter
@@ -29,26 +46,100 @@ pub trait ColorSet: ColorFmt {
     /// Sets color which located at specified index
     fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType);
 
-    //// Sets colors at specified range by pieces of specified length with specified stride
-    //fn set_colors(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: &mut dyn Iterator<Item = Self::ColorType>);
+    /// Sets colors at specified range by pieces of specified length with specified stride
+    ///
+    /// Setting stops when either range or colors is exhausted.
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        for (index, color) in ColorSpans::new(range, length, stride).flatten().zip(colors) {
+            self.set_color(buffer, index, color);
+        }
+    }
+
+    /// Fills colors at specified range by single color
+    ///
+    /// The color packs only once and copies to all fully covered bytes.
+    fn fill_colors(&self, buffer: &mut [u8], range: Range<usize>, color: Self::ColorType)
+    where
+        Self::ColorType: Clone,
+    {
+        let bits = Self::ColorBits::USIZE;
+        // number of colors and bytes in minimal byte-aligned pattern
+        let colors = 8 >> bits.trailing_zeros().min(3);
+        let bytes = colors * bits / 8;
+
+        let start = (range.start.div_ceil(colors) * colors).min(range.end);
+        let end = (range.end / colors * colors).max(start);
+
+        for index in (range.start..start).chain(end..range.end) {
+            self.set_color(buffer, index, color.clone());
+        }
+
+        if start < end {
+            let mut pattern = [0u8; 32];
+            let pattern = &mut pattern[..bytes];
+
+            for index in 0..colors {
+                self.set_color(pattern, index, color.clone());
+            }
+
+            let buffer = &mut buffer[start / colors * bytes..end / colors * bytes];
+
+            if pattern.iter().all(|byte| *byte == pattern[0]) {
+                buffer.fill(pattern[0]);
+            } else {
+                for chunk in buffer.chunks_exact_mut(bytes) {
+                    chunk.copy_from_slice(pattern);
+                }
+            }
+        }
+    }
 }
 
 pub trait ColorBuf {
-    type ColorFmt: ColorFmt;
+    type ColorFmt: ColorGet;
 
     /// Number of colors in buffer
     fn len(&self) -> usize;
 
+    /// Check that buffer has no colors
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     /// Get color from buffer by index
     fn get(&self, index: usize) -> <Self::ColorFmt as ColorFmt>::ColorType;
 
-    //// Get colors at specified range by pieces of specified length with specified stride
-    //fn gets(&self, range: Range<usize>, length: usize, stride: usize) -> Fmt::ColorIter;
+    /// Get colors at specified range by pieces of specified length with specified stride
+    fn gets(&self, range: Range<usize>, length: usize, stride: usize) -> ColorsIter<'_, Self::ColorFmt>;
+
+    /// Get packed color data
+    fn bytes(&self) -> &[u8];
+
+    /// Get packed size of color in number of bits
+    fn color_bits(&self) -> usize {
+        <Self::ColorFmt as ColorFmt>::ColorBits::USIZE
+    }
 }
 
 pub trait ColorBufMut: ColorBuf {
     /// Set color in buffer by index
     fn set(&mut self, index: usize, color: <Self::ColorFmt as ColorFmt>::ColorType);
+
+    /// Set colors at specified range by pieces of specified length with specified stride
+    fn sets<Colors>(&mut self, range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = <Self::ColorFmt as ColorFmt>::ColorType>;
+
+    /// Fill colors at specified range by single color
+    fn fill(&mut self, range: Range<usize>, color: <Self::ColorFmt as ColorFmt>::ColorType)
+    where
+        <Self::ColorFmt as ColorFmt>::ColorType: Clone;
+
+    /// Get mutable packed color data
+    fn bytes_mut(&mut self) -> &mut [u8];
 }
 
 impl<Fmt> ColorFmt for &Fmt
@@ -61,6 +152,10 @@ where
     fn num_colors(&self, buffer: &[u8]) -> usize {
         (*self).num_colors(buffer)
     }
+
+    fn color_bits(&self) -> usize {
+        (*self).color_bits()
+    }
 }
 
 impl<Fmt> ColorGet for &Fmt
@@ -79,39 +174,104 @@ where
     fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
         (*self).set_color(buffer, index, color);
     }
+
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        (*self).set_colors(buffer, range, length, stride, colors);
+    }
+
+    fn fill_colors(&self, buffer: &mut [u8], range: Range<usize>, color: Self::ColorType)
+    where
+        Self::ColorType: Clone,
+    {
+        (*self).fill_colors(buffer, range, color);
+    }
 }
 
-impl<Fmt, Buf> ColorBuf for (Fmt, Buf)
+impl<Buf> ColorBuf for &Buf
 where
-    Fmt: ColorGet,
-    Buf: AsRef<[u8]>,
+    Buf: ColorBuf,
 {
-    type ColorFmt = Fmt;
+    type ColorFmt = Buf::ColorFmt;
 
     fn len(&self) -> usize {
-        self.0.num_colors(self.1.as_ref())
+        (*self).len()
     }
 
-    fn get(&self, index: usize) -> Fmt::ColorType {
-        self.0.get_color(self.1.as_ref(), index)
+    fn get(&self, index: usize) -> <Self::ColorFmt as ColorFmt>::ColorType {
+        (*self).get(index)
     }
 
-    /*fn gets(&self, range: Range<usize>, length: usize, stride: usize) -> Fmt::ColorIter {
-        self.0.get_colors(self.1.as_ref(), range, length, stride)
-    }*/
+    fn gets(&self, range: Range<usize>, length: usize, stride: usize) -> ColorsIter<'_, Self::ColorFmt> {
+        (**self).gets(range, length, stride)
+    }
+
+    fn bytes(&self) -> &[u8] {
+        (**self).bytes()
+    }
+
+    fn color_bits(&self) -> usize {
+        (**self).color_bits()
+    }
 }
 
-impl<Fmt, Buf> ColorBufMut for (Fmt, Buf)
+impl<Buf> ColorBuf for &mut Buf
 where
-    Fmt: ColorGet + ColorSet,
-    Buf: AsRef<[u8]> + AsMut<[u8]>,
+    Buf: ColorBuf,
 {
-    fn set(&mut self, index: usize, color: Fmt::ColorType) {
-        self.0.set_color(self.1.as_mut(), index, color)
+    type ColorFmt = Buf::ColorFmt;
+
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+
+    fn get(&self, index: usize) -> <Self::ColorFmt as ColorFmt>::ColorType {
+        (**self).get(index)
+    }
+
+    fn gets(&self, range: Range<usize>, length: usize, stride: usize) -> ColorsIter<'_, Self::ColorFmt> {
+        (**self).gets(range, length, stride)
+    }
+
+    fn bytes(&self) -> &[u8] {
+        (**self).bytes()
+    }
+
+    fn color_bits(&self) -> usize {
+        (**self).color_bits()
     }
 }
 
-/*impl<Fmt, Buf> ColorBuf for (&Fmt, Buf)
+impl<Buf> ColorBufMut for &mut Buf
+where
+    Buf: ColorBufMut,
+{
+    fn set(&mut self, index: usize, color: <Self::ColorFmt as ColorFmt>::ColorType) {
+        (**self).set(index, color)
+    }
+
+    fn sets<Colors>(&mut self, range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = <Self::ColorFmt as ColorFmt>::ColorType>,
+    {
+        (**self).sets(range, length, stride, colors)
+    }
+
+    fn fill(&mut self, range: Range<usize>, color: <Self::ColorFmt as ColorFmt>::ColorType)
+    where
+        <Self::ColorFmt as ColorFmt>::ColorType: Clone,
+    {
+        (**self).fill(range, color)
+    }
+
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        (**self).bytes_mut()
+    }
+}
+
+impl<Fmt, Buf> ColorBuf for (Fmt, Buf)
 where
     Fmt: ColorGet,
     Buf: AsRef<[u8]>,
@@ -126,17 +286,122 @@ where
         self.0.get_color(self.1.as_ref(), index)
     }
 
-    /*fn gets(&self, range: Range<usize>, length: usize, stride: usize) -> Fmt::ColorIter {
+    fn gets(&self, range: Range<usize>, length: usize, stride: usize) -> ColorsIter<'_, Fmt> {
         self.0.get_colors(self.1.as_ref(), range, length, stride)
-    }*/
+    }
+
+    fn bytes(&self) -> &[u8] {
+        self.1.as_ref()
+    }
+
+    fn color_bits(&self) -> usize {
+        self.0.color_bits()
+    }
 }
 
-impl<Fmt, Buf> ColorBufMut for (&Fmt, Buf)
+impl<Fmt, Buf> ColorBufMut for (Fmt, Buf)
 where
-    Fmt: ColorSet,
-    Buf: AsMut<[u8]> + AsRef<Fmt>,
+    Fmt: ColorGet + ColorSet,
+    Buf: AsRef<[u8]> + AsMut<[u8]>,
 {
     fn set(&mut self, index: usize, color: Fmt::ColorType) {
         self.0.set_color(self.1.as_mut(), index, color)
     }
-}*/
+
+    fn sets<Colors>(&mut self, range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Fmt::ColorType>,
+    {
+        self.0.set_colors(self.1.as_mut(), range, length, stride, colors)
+    }
+
+    fn fill(&mut self, range: Range<usize>, color: Fmt::ColorType)
+    where
+        Fmt::ColorType: Clone,
+    {
+        self.0.fill_colors(self.1.as_mut(), range, color)
+    }
+
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        self.1.as_mut()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ConstDefault, GS, RGB, format};
+
+    #[test]
+    fn buf_colors() {
+        let mut buf = (format::GS8, [0u8; 6]);
+
+        buf.sets(0..6, 2, 3, [1, 2, 3, 4].iter().map(|v| GS::new(*v)));
+
+        assert_eq!(&buf.1, &[1, 2, 0, 3, 4, 0]);
+        assert!(buf.gets(1..6, 1, 3).eq([GS::new(2), GS::new(4)].iter().cloned()));
+        assert!(!buf.is_empty());
+        assert!((format::GS8, [0u8; 0]).is_empty());
+    }
+
+    #[test]
+    fn default_colors() {
+        struct Nibbles;
+
+        impl ColorFmt for Nibbles {
+            type ColorType = u8;
+            type ColorBits = typenum::U4;
+
+            fn num_colors(&self, buffer: &[u8]) -> usize {
+                buffer.len() * 2
+            }
+        }
+
+        impl ColorGet for Nibbles {
+            fn get_color(&self, buffer: &[u8], index: usize) -> u8 {
+                buffer[index / 2] >> (index % 2 * 4) & 0xf
+            }
+        }
+
+        assert!(Nibbles.get_colors(&[0x21, 0x43, 0x65], 1..6, 2, 3).eq([2, 3, 5, 6].iter().cloned()));
+    }
+
+    #[test]
+    fn fill_bytes() {
+        let mut buf = (format::GS8, [0u8; 6]);
+
+        buf.fill(1..5, GS::new(7));
+
+        assert_eq!(&buf.1, &[0, 7, 7, 7, 7, 0]);
+
+        let mut buf = (format::RGB565, [0u8; 8]);
+
+        buf.fill(1..4, RGB::new(255, 0, 255));
+
+        assert_eq!(&buf.1, &[0, 0, 0xf8, 0x1f, 0xf8, 0x1f, 0xf8, 0x1f]);
+    }
+
+    #[test]
+    fn fill_bits() {
+        let mut buf = (<format::GS2>::DEFAULT, [0u8; 4]);
+
+        buf.fill(3..14, GS::new(255));
+
+        assert_eq!(&buf.1, &[0b11_00_00_00, 0xff, 0xff, 0b00_00_11_11]);
+
+        let mut buf = (<format::GS1>::DEFAULT, [0u8; 2]);
+
+        buf.fill(2..5, GS::new(255));
+
+        assert_eq!(&buf.1, &[0b0001_1100, 0]);
+    }
+
+    #[test]
+    fn fill_rgb444() {
+        let mut buf = (format::RGB444, [0u8; 9]);
+
+        buf.fill(1..6, RGB::new(0x10, 0x20, 0x30));
+
+        assert_eq!(&buf.1, &[0, 0x01, 0x23, 0x12, 0x31, 0x23, 0x12, 0x31, 0x23]);
+    }
+}
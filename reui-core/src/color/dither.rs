@@ -0,0 +1,213 @@
+use crate::{ConstDefault, Point};
+use super::{ColorFmt, BitOrder, GS, GSA, RGB, RGBA};
+use super::format::*;
+
+/// Ordered dithering matrix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bayer {
+    /// Bayer matrix 2x2
+    Bayer2,
+    /// Bayer matrix 4x4
+    Bayer4,
+    /// Bayer matrix 8x8
+    Bayer8,
+}
+
+impl ConstDefault for Bayer {
+    const DEFAULT: Self = Bayer::Bayer4;
+}
+
+impl Default for Bayer {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl Bayer {
+    /// Get number of bits of matrix size
+    fn order(&self) -> u32 {
+        match self {
+            Bayer::Bayer2 => 1,
+            Bayer::Bayer4 => 2,
+            Bayer::Bayer8 => 3,
+        }
+    }
+
+    /// Get index of matrix cell for point
+    pub fn index(&self, point: Point<i32>) -> u8 {
+        let (x, y) = (point.x as u32, point.y as u32);
+        let mut index = 0;
+
+        // interleaved bits of `x ^ y` and `y` in reverse order
+        for bit in 0..self.order() {
+            index = (index << 2) | ((((x ^ y) >> bit) & 1) << 1) | ((y >> bit) & 1);
+        }
+
+        index as u8
+    }
+
+    /// Get threshold for point as fraction of 256
+    pub fn threshold(&self, point: Point<i32>) -> u8 {
+        let order = self.order() * 2;
+        (((self.index(point) as u32 * 2 + 1) << 8 >> order) / 2) as u8
+    }
+}
+
+/// Reduce channel value to specified number of bits using threshold as fraction of 256
+///
+/// The result is expanded back to 8 bits, so formats which truncates channels keeps it exactly.
+/// The threshold 128 gives rounding to nearest level.
+pub fn quantize(value: u8, bits: u8, threshold: u8) -> u8 {
+    if bits >= 8 {
+        return value;
+    }
+
+    let levels = (1u32 << bits) - 1;
+    let level = ((value as u32 * levels * 256 / 255 + threshold as u32) >> 8).min(levels);
+
+    (level * 255 / levels) as u8
+}
+
+/// Color format which can reduce colors to its depth
+pub trait ColorDepth: ColorFmt {
+    /// Reduce color to depth of format using threshold as fraction of 256
+    fn reduce(color: RGBA, threshold: u8) -> Self::ColorType;
+}
+
+impl<Fmt: ColorDepth> ColorDepth for &Fmt {
+    fn reduce(color: RGBA, threshold: u8) -> Self::ColorType {
+        Fmt::reduce(color, threshold)
+    }
+}
+
+macro_rules! depth_impls {
+    ($(rgb $rgb_type:ty: $r:literal, $g:literal, $b:literal;)*
+     $(rgba $rgba_type:ty: $ra:literal, $ga:literal, $ba:literal;)*
+     $(gs $gs_type:ty: $v:literal;)*
+     $(gsa $gsa_type:ty: $va:literal;)*) => {
+        $(
+            impl ColorDepth for $rgb_type {
+                fn reduce(color: RGBA, threshold: u8) -> RGB {
+                    RGB::new(quantize(color.r, $r, threshold), quantize(color.g, $g, threshold), quantize(color.b, $b, threshold))
+                }
+            }
+        )*
+        $(
+            impl ColorDepth for $rgba_type {
+                fn reduce(color: RGBA, threshold: u8) -> RGBA {
+                    RGBA::new(quantize(color.r, $ra, threshold), quantize(color.g, $ga, threshold), quantize(color.b, $ba, threshold), color.a)
+                }
+            }
+        )*
+        $(
+            impl<Order: BitOrder> ColorDepth for $gs_type {
+                fn reduce(color: RGBA, threshold: u8) -> Self::ColorType {
+                    GS::new(quantize(RGB::from(color).luma(), $v, threshold)).into()
+                }
+            }
+        )*
+        $(
+            impl<Order: BitOrder> ColorDepth for $gsa_type {
+                fn reduce(color: RGBA, threshold: u8) -> GSA {
+                    GSA::new(quantize(RGB::from(color).luma(), $va, threshold), color.a)
+                }
+            }
+        )*
+    };
+}
+
+depth_impls! {
+    rgb RGB332: 3, 3, 2;
+    rgb RGB444: 4, 4, 4;
+    rgb RGB565: 5, 6, 5;
+    rgb RGB888: 8, 8, 8;
+    rgba RGBA4444: 4, 4, 4;
+    rgba RGBA5551: 5, 5, 5;
+    rgba RGBA8888: 8, 8, 8;
+    gs BW1<Order>: 1;
+    gs GS1<Order>: 1;
+    gs GS2<Order>: 2;
+    gs GS4<Order>: 4;
+    gsa GSA11<Order>: 1;
+    gsa GSA22<Order>: 2;
+    gsa GSA31<Order>: 3;
+}
+
+impl ColorDepth for GS8 {
+    fn reduce(color: RGBA, _threshold: u8) -> GS {
+        GS::new(RGB::from(color).luma())
+    }
+}
+
+impl ColorDepth for GSA44 {
+    fn reduce(color: RGBA, threshold: u8) -> GSA {
+        GSA::new(quantize(RGB::from(color).luma(), 4, threshold), color.a)
+    }
+}
+
+impl ColorDepth for GSA71 {
+    fn reduce(color: RGBA, threshold: u8) -> GSA {
+        GSA::new(quantize(RGB::from(color).luma(), 7, threshold), color.a)
+    }
+}
+
+impl ColorDepth for GSA88 {
+    fn reduce(color: RGBA, _threshold: u8) -> GSA {
+        GSA::new(RGB::from(color).luma(), color.a)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::BW;
+
+    #[test]
+    fn matrix() {
+        let index = |bayer: Bayer, x, y| bayer.index(Point::new(x, y));
+
+        assert_eq!([index(Bayer::Bayer2, 0, 0), index(Bayer::Bayer2, 1, 0), index(Bayer::Bayer2, 0, 1), index(Bayer::Bayer2, 1, 1)], [0, 2, 3, 1]);
+        assert_eq!([index(Bayer::Bayer4, 1, 0), index(Bayer::Bayer4, 2, 0), index(Bayer::Bayer4, 0, 1), index(Bayer::Bayer4, 3, 3)], [8, 2, 12, 5]);
+        assert_eq!([index(Bayer::Bayer8, 0, 0), index(Bayer::Bayer8, 1, 0), index(Bayer::Bayer8, 7, 7), index(Bayer::Bayer8, 8, 0)], [0, 32, 21, 0]);
+        assert_eq!(index(Bayer::Bayer4, -1, -1), index(Bayer::Bayer4, 3, 3));
+
+        let mut seen = [false; 64];
+        for y in 0..8 {
+            for x in 0..8 {
+                seen[index(Bayer::Bayer8, x, y) as usize] = true;
+            }
+        }
+        assert!(seen.iter().all(|seen| *seen));
+
+        assert_eq!(Bayer::Bayer2.threshold(Point::new(0, 0)), 32);
+        assert_eq!(Bayer::Bayer2.threshold(Point::new(0, 1)), 224);
+        assert_eq!(Bayer::Bayer4.threshold(Point::new(0, 0)), 8);
+        assert_eq!(Bayer::Bayer4.threshold(Point::new(0, 3)), 248);
+    }
+
+    #[test]
+    fn levels() {
+        assert_eq!(quantize(100, 8, 255), 100);
+        assert_eq!(quantize(100, 1, 128), 0);
+        assert_eq!(quantize(128, 1, 128), 255);
+        assert_eq!(quantize(100, 1, 200), 255);
+        assert_eq!(quantize(100, 2, 0), 85);
+        assert_eq!(quantize(255, 2, 255), 255);
+        assert_eq!(quantize(100, 5, 128), 98);
+
+        // average of dithered values is close to original
+        for value in 0..=255 {
+            let sum: u32 = (0..16).map(|t| quantize(value, 2, t * 16 + 8) as u32).sum();
+            assert!((sum as i32 / 16 - value as i32).abs() <= 6, "{}", value);
+        }
+    }
+
+    #[test]
+    fn reduce() {
+        assert_eq!(RGB565::reduce(RGBA::new(100, 100, 100, 10), 128), RGB::new(98, 101, 98));
+        assert_eq!(RGBA4444::reduce(RGBA::new(100, 100, 100, 10), 0), RGBA::new(85, 85, 85, 10));
+        assert_eq!(<GS2>::reduce(RGBA::new(255, 0, 0, 255), 128), GS::new(85));
+        assert_eq!(<BW1>::reduce(RGBA::new(100, 100, 100, 255), 200), BW::from(true));
+        assert_eq!(GSA88::reduce(RGBA::new(0, 255, 0, 7), 0), GSA::new(149, 7));
+    }
+}
@@ -0,0 +1,177 @@
+use core::ops::Range;
+use crate::{ConstDefault, Size, PixelView};
+use super::{ColorFmt, ColorGet, ColorSet, RGBA};
+use super::format::*;
+
+/// Color format selected at runtime
+///
+/// The colors is converted from and to RGBA. The bit-packed formats uses LSB-first order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DynFormat {
+    BW1,
+    GS1,
+    GS2,
+    GS4,
+    GS8,
+    GSA11,
+    GSA22,
+    GSA31,
+    GSA44,
+    GSA71,
+    GSA88,
+    RGB332,
+    RGB444,
+    RGB565,
+    RGB888,
+    RGBA4444,
+    RGBA5551,
+    RGBA8888,
+}
+
+/// Call function with static format which corresponds to dynamic format
+macro_rules! dispatch {
+    ($format: expr, $fmt: ident => $expr: expr) => {
+        match $format {
+            DynFormat::BW1 => { let $fmt = <BW1>::DEFAULT; $expr }
+            DynFormat::GS1 => { let $fmt = <GS1>::DEFAULT; $expr }
+            DynFormat::GS2 => { let $fmt = <GS2>::DEFAULT; $expr }
+            DynFormat::GS4 => { let $fmt = <GS4>::DEFAULT; $expr }
+            DynFormat::GS8 => { let $fmt = GS8; $expr }
+            DynFormat::GSA11 => { let $fmt = <GSA11>::DEFAULT; $expr }
+            DynFormat::GSA22 => { let $fmt = <GSA22>::DEFAULT; $expr }
+            DynFormat::GSA31 => { let $fmt = <GSA31>::DEFAULT; $expr }
+            DynFormat::GSA44 => { let $fmt = GSA44; $expr }
+            DynFormat::GSA71 => { let $fmt = GSA71; $expr }
+            DynFormat::GSA88 => { let $fmt = GSA88; $expr }
+            DynFormat::RGB332 => { let $fmt = RGB332; $expr }
+            DynFormat::RGB444 => { let $fmt = RGB444; $expr }
+            DynFormat::RGB565 => { let $fmt = RGB565; $expr }
+            DynFormat::RGB888 => { let $fmt = RGB888; $expr }
+            DynFormat::RGBA4444 => { let $fmt = RGBA4444; $expr }
+            DynFormat::RGBA5551 => { let $fmt = RGBA5551; $expr }
+            DynFormat::RGBA8888 => { let $fmt = RGBA8888; $expr }
+        }
+    };
+}
+
+impl DynFormat {
+    /// Get format by name of type (i.e. `"RGB565"`)
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "BW1" => DynFormat::BW1,
+            "GS1" => DynFormat::GS1,
+            "GS2" => DynFormat::GS2,
+            "GS4" => DynFormat::GS4,
+            "GS8" => DynFormat::GS8,
+            "GSA11" => DynFormat::GSA11,
+            "GSA22" => DynFormat::GSA22,
+            "GSA31" => DynFormat::GSA31,
+            "GSA44" => DynFormat::GSA44,
+            "GSA71" => DynFormat::GSA71,
+            "GSA88" => DynFormat::GSA88,
+            "RGB332" => DynFormat::RGB332,
+            "RGB444" => DynFormat::RGB444,
+            "RGB565" => DynFormat::RGB565,
+            "RGB888" => DynFormat::RGB888,
+            "RGBA4444" => DynFormat::RGBA4444,
+            "RGBA5551" => DynFormat::RGBA5551,
+            "RGBA8888" => DynFormat::RGBA8888,
+            _ => return None,
+        })
+    }
+
+    /// Create pixel view of specified size over buffer with colors in this format
+    pub const fn view<Buf>(self, size: Size<usize>, data: Buf) -> PixelView<(Self, Buf)> {
+        PixelView::new(size, (self, data))
+    }
+}
+
+impl ColorFmt for DynFormat {
+    type ColorType = RGBA;
+    type ColorBits = typenum::U32;
+
+    fn num_colors(&self, buffer: &[u8]) -> usize {
+        buffer.len() * 8 / self.color_bits()
+    }
+
+    fn color_bits(&self) -> usize {
+        dispatch!(self, fmt => fmt.color_bits())
+    }
+}
+
+// the conversions are required for formats with other color types
+#[allow(clippy::useless_conversion)]
+impl ColorGet for DynFormat {
+    fn get_color(&self, buffer: &[u8], index: usize) -> RGBA {
+        dispatch!(self, fmt => fmt.get_color(buffer, index).into())
+    }
+}
+
+#[allow(clippy::useless_conversion)]
+impl ColorSet for DynFormat {
+    fn set_color(&self, buffer: &mut [u8], index: usize, color: RGBA) {
+        dispatch!(self, fmt => fmt.set_color(buffer, index, color.into()))
+    }
+
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = RGBA>,
+    {
+        dispatch!(self, fmt => fmt.set_colors(buffer, range, length, stride, colors.into_iter().map(Into::into)))
+    }
+
+    fn fill_colors(&self, buffer: &mut [u8], range: Range<usize>, color: RGBA) {
+        dispatch!(self, fmt => fmt.fill_colors(buffer, range, color.into()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Point, RGB};
+
+    #[test]
+    fn names() {
+        assert_eq!(DynFormat::from_name("RGB565"), Some(DynFormat::RGB565));
+        assert_eq!(DynFormat::from_name("GSA71"), Some(DynFormat::GSA71));
+        assert_eq!(DynFormat::from_name("RGB666"), None);
+    }
+
+    #[test]
+    fn colors() {
+        let format = DynFormat::RGB565;
+        let mut buffer = [0u8; 6];
+
+        assert_eq!(format.color_bits(), 16);
+        assert_eq!(format.num_colors(&buffer), 3);
+
+        format.fill_colors(&mut buffer, 0..3, RGB::new(0, 252, 0).into());
+        format.set_color(&mut buffer, 2, RGBA::new(255, 0, 0, 255));
+
+        assert_eq!(buffer, [0b0000_0111, 0b1110_0000, 0b0000_0111, 0b1110_0000, 0b1111_1000, 0]);
+        assert_eq!(format.get_color(&buffer, 0), RGBA::new(0, 252, 0, 255));
+
+        let format = DynFormat::GS4;
+
+        assert_eq!(format.num_colors(&buffer), 12);
+
+        format.set_colors(&mut buffer, 0..2, 2, 2, [RGBA::new(255, 255, 255, 255), RGBA::new(0, 0, 0, 255)].iter().cloned());
+
+        assert_eq!(buffer[0], 0x0f);
+    }
+
+    #[test]
+    fn view() {
+        let mut view = DynFormat::BW1.view(Size::new(10, 2), [0u8; 4]).with_byte_stride(2);
+
+        view.fill(RGBA::new(255, 255, 255, 255));
+        view.set(Point::new(9, 1), RGBA::new(0, 0, 0, 255));
+
+        assert_eq!(view.stride(), 16);
+        assert_eq!(view.unwrap().1, [0xff, 0x03, 0xff, 0x01]);
+
+        let view = DynFormat::RGB332.view(Size::new(2, 1), [0b1110_0000, 0b0000_0011]);
+
+        assert_eq!(view.get(Point::new(1, 0)), RGBA::new(0, 0, 255, 255));
+    }
+}
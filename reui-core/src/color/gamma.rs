@@ -0,0 +1,319 @@
+use core::ops::Range;
+use crate::ConstDefault;
+use super::{ColorFmt, ColorGet, ColorSet, BW, GS, GSA, RGB, RGBA};
+
+/// The linear light values of sRGB encoded values scaled to 16 bits
+pub const SRGB_TO_LINEAR: [u16; 256] = [
+    0, 20, 40, 60, 80, 99, 119, 139, 159, 179, 199, 219, 241, 264, 288, 313,
+    340, 367, 396, 427, 458, 491, 526, 562, 599, 637, 677, 718, 761, 805, 851, 898,
+    947, 997, 1048, 1101, 1156, 1212, 1270, 1330, 1391, 1453, 1517, 1583, 1651, 1720, 1790, 1863,
+    1937, 2013, 2090, 2170, 2250, 2333, 2418, 2504, 2592, 2681, 2773, 2866, 2961, 3058, 3157, 3258,
+    3360, 3464, 3570, 3678, 3788, 3900, 4014, 4129, 4247, 4366, 4488, 4611, 4736, 4864, 4993, 5124,
+    5257, 5392, 5530, 5669, 5810, 5953, 6099, 6246, 6395, 6547, 6700, 6856, 7014, 7174, 7335, 7500,
+    7666, 7834, 8004, 8177, 8352, 8528, 8708, 8889, 9072, 9258, 9445, 9635, 9828, 10022, 10219, 10417,
+    10619, 10822, 11028, 11235, 11446, 11658, 11873, 12090, 12309, 12530, 12754, 12980, 13209, 13440, 13673, 13909,
+    14146, 14387, 14629, 14874, 15122, 15371, 15623, 15878, 16135, 16394, 16656, 16920, 17187, 17456, 17727, 18001,
+    18277, 18556, 18837, 19121, 19407, 19696, 19987, 20281, 20577, 20876, 21177, 21481, 21787, 22096, 22407, 22721,
+    23038, 23357, 23678, 24002, 24329, 24658, 24990, 25325, 25662, 26001, 26344, 26688, 27036, 27386, 27739, 28094,
+    28452, 28813, 29176, 29542, 29911, 30282, 30656, 31033, 31412, 31794, 32179, 32567, 32957, 33350, 33745, 34143,
+    34544, 34948, 35355, 35764, 36176, 36591, 37008, 37429, 37852, 38278, 38706, 39138, 39572, 40009, 40449, 40891,
+    41337, 41785, 42236, 42690, 43147, 43606, 44069, 44534, 45002, 45473, 45947, 46423, 46903, 47385, 47871, 48359,
+    48850, 49344, 49841, 50341, 50844, 51349, 51858, 52369, 52884, 53401, 53921, 54445, 54971, 55500, 56032, 56567,
+    57105, 57646, 58190, 58737, 59287, 59840, 60396, 60955, 61517, 62082, 62650, 63221, 63795, 64372, 64952, 65535,
+];
+
+/// Convert sRGB encoded value to linear light value
+pub fn srgb_to_linear(value: u8) -> u16 {
+    SRGB_TO_LINEAR[value as usize]
+}
+
+/// Convert linear light value to nearest sRGB encoded value
+pub fn linear_to_srgb(value: u16) -> u8 {
+    match SRGB_TO_LINEAR.binary_search(&value) {
+        Ok(index) => index as u8,
+        Err(0) => 0,
+        Err(256) => 255,
+        Err(index) => {
+            if value - SRGB_TO_LINEAR[index - 1] < SRGB_TO_LINEAR[index] - value {
+                index as u8 - 1
+            } else {
+                index as u8
+            }
+        }
+    }
+}
+
+/// Per-channel color correction table
+///
+/// Maps each channel value to value which should be written to display
+/// to compensate its response curve.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GammaTable {
+    pub r: [u8; 256],
+    pub g: [u8; 256],
+    pub b: [u8; 256],
+}
+
+impl ConstDefault for GammaTable {
+    const DEFAULT: Self = Self::IDENTITY;
+}
+
+impl Default for GammaTable {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+const fn identity() -> [u8; 256] {
+    let mut table = [0u8; 256];
+    let mut index = 0;
+
+    while index < 256 {
+        table[index] = index as u8;
+        index += 1;
+    }
+
+    table
+}
+
+impl GammaTable {
+    /// The table which does not change colors
+    pub const IDENTITY: Self = Self { r: identity(), g: identity(), b: identity() };
+
+    /// Create table using separate tables for channels
+    pub const fn new(r: [u8; 256], g: [u8; 256], b: [u8; 256]) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Create table using same curve for all channels
+    pub fn from_fn<F: Fn(u8) -> u8>(curve: F) -> Self {
+        let mut table = Self::IDENTITY;
+
+        for index in 0..256 {
+            let value = curve(index as u8);
+            table.r[index] = value;
+            table.g[index] = value;
+            table.b[index] = value;
+        }
+
+        table
+    }
+
+    /// Correct gray value using green channel table
+    pub fn gray(&self, value: u8) -> u8 {
+        self.g[value as usize]
+    }
+
+    /// Restore gray value which was corrected using green channel table
+    pub fn ungray(&self, value: u8) -> u8 {
+        invert(&self.g, value)
+    }
+}
+
+/// Find the least value which maps to specified corrected value or above
+///
+/// The channel table is expected to be non-decreasing.
+fn invert(channel: &[u8; 256], value: u8) -> u8 {
+    channel.partition_point(|corrected| *corrected < value).min(255) as u8
+}
+
+/// Color which can be corrected using table
+pub trait ColorCorrect {
+    /// Apply correction table to color
+    fn correct(self, table: &GammaTable) -> Self;
+
+    /// Apply inverse of correction table to color
+    fn uncorrect(self, table: &GammaTable) -> Self;
+}
+
+impl ColorCorrect for BW {
+    fn correct(self, _table: &GammaTable) -> Self {
+        self
+    }
+
+    fn uncorrect(self, _table: &GammaTable) -> Self {
+        self
+    }
+}
+
+impl ColorCorrect for GS {
+    fn correct(self, table: &GammaTable) -> Self {
+        GS::new(table.gray(self.v))
+    }
+
+    fn uncorrect(self, table: &GammaTable) -> Self {
+        GS::new(table.ungray(self.v))
+    }
+}
+
+impl ColorCorrect for GSA {
+    fn correct(self, table: &GammaTable) -> Self {
+        GSA::new(table.gray(self.v), self.a)
+    }
+
+    fn uncorrect(self, table: &GammaTable) -> Self {
+        GSA::new(table.ungray(self.v), self.a)
+    }
+}
+
+impl ColorCorrect for RGB {
+    fn correct(self, table: &GammaTable) -> Self {
+        RGB::new(table.r[self.r as usize], table.g[self.g as usize], table.b[self.b as usize])
+    }
+
+    fn uncorrect(self, table: &GammaTable) -> Self {
+        RGB::new(invert(&table.r, self.r), invert(&table.g, self.g), invert(&table.b, self.b))
+    }
+}
+
+impl ColorCorrect for RGBA {
+    fn correct(self, table: &GammaTable) -> Self {
+        RGBA::new(table.r[self.r as usize], table.g[self.g as usize], table.b[self.b as usize], self.a)
+    }
+
+    fn uncorrect(self, table: &GammaTable) -> Self {
+        RGBA::new(invert(&table.r, self.r), invert(&table.g, self.g), invert(&table.b, self.b), self.a)
+    }
+}
+
+/// Color format which corrects colors when writing
+///
+/// Colors is read back using inverse correction, so blending over existing pixels
+/// does not apply correction twice. The tables should be non-decreasing to be inverted.
+#[derive(Debug, Clone, Copy)]
+pub struct Corrected<'t, Fmt> {
+    pub format: Fmt,
+    pub table: &'t GammaTable,
+}
+
+impl<'t, Fmt> Corrected<'t, Fmt> {
+    /// Wrap color format using correction table
+    pub fn new(format: Fmt, table: &'t GammaTable) -> Self {
+        Self { format, table }
+    }
+}
+
+impl<'t, Fmt: ColorFmt> ColorFmt for Corrected<'t, Fmt> {
+    type ColorType = Fmt::ColorType;
+    type ColorBits = Fmt::ColorBits;
+
+    fn num_colors(&self, buffer: &[u8]) -> usize {
+        self.format.num_colors(buffer)
+    }
+
+    fn color_bits(&self) -> usize {
+        self.format.color_bits()
+    }
+}
+
+impl<'t, Fmt> ColorGet for Corrected<'t, Fmt>
+where
+    Fmt: ColorGet,
+    Fmt::ColorType: ColorCorrect,
+{
+    fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
+        self.format.get_color(buffer, index).uncorrect(self.table)
+    }
+}
+
+impl<'t, Fmt> ColorSet for Corrected<'t, Fmt>
+where
+    Fmt: ColorSet,
+    Fmt::ColorType: ColorCorrect,
+{
+    fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
+        self.format.set_color(buffer, index, color.correct(self.table));
+    }
+
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        let table = self.table;
+        self.format.set_colors(buffer, range, length, stride, colors.into_iter().map(|color| color.correct(table)));
+    }
+
+    fn fill_colors(&self, buffer: &mut [u8], range: Range<usize>, color: Self::ColorType)
+    where
+        Self::ColorType: Clone,
+    {
+        self.format.fill_colors(buffer, range, color.correct(self.table));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{format, DynFormat, PixelView, Point, Rect, Size};
+
+    #[test]
+    fn srgb() {
+        assert_eq!(srgb_to_linear(0), 0);
+        assert_eq!(srgb_to_linear(255), 65535);
+        assert_eq!(srgb_to_linear(128), 14146);
+        assert_eq!(linear_to_srgb(32768), 188);
+        assert_eq!(linear_to_srgb(65535), 255);
+        assert_eq!(linear_to_srgb(1), 0);
+        assert_eq!(linear_to_srgb(15), 1);
+
+        for value in 0..=255 {
+            assert_eq!(linear_to_srgb(srgb_to_linear(value)), value);
+        }
+    }
+
+    #[test]
+    fn table() {
+        let table = GammaTable::from_fn(|value| value / 2);
+
+        assert_eq!(GammaTable::IDENTITY.r[200], 200);
+        assert_eq!(RGB::new(10, 20, 30).correct(&GammaTable::IDENTITY), RGB::new(10, 20, 30));
+        assert_eq!(RGBA::new(10, 20, 30, 40).correct(&table), RGBA::new(5, 10, 15, 40));
+        assert_eq!(GS::new(255).correct(&table), GS::new(127));
+        assert_eq!(GS::new(127).uncorrect(&table), GS::new(254));
+        assert_eq!(RGB::new(0, 1, 127).uncorrect(&table), RGB::new(0, 2, 254));
+        assert_eq!(RGB::new(10, 20, 30).uncorrect(&GammaTable::IDENTITY), RGB::new(10, 20, 30));
+    }
+
+    #[test]
+    fn corrected() {
+        let mut table = GammaTable::IDENTITY;
+        table.r[255] = 100;
+
+        let fmt = Corrected::new(format::RGB888, &table);
+        let mut buf = [0u8; 9];
+
+        fmt.set_color(&mut buf, 0, RGB::new(255, 255, 0));
+        fmt.set_colors(&mut buf, 1..3, 2, 2, [RGB::new(255, 1, 2), RGB::new(3, 4, 5)].iter().cloned());
+
+        assert_eq!(buf, [100, 255, 0, 100, 1, 2, 3, 4, 5]);
+        assert_eq!(fmt.get_color(&buf, 0), RGB::new(100, 255, 0));
+
+        fmt.fill_colors(&mut buf, 0..3, RGB::new(255, 0, 0));
+
+        assert_eq!(buf, [100, 0, 0, 100, 0, 0, 100, 0, 0]);
+        assert!(fmt.get_colors(&buf, 1..3, 2, 2).eq([RGB::new(100, 0, 0); 2]));
+    }
+
+    #[test]
+    fn corrected_blend() {
+        let table = GammaTable::from_fn(|value| value / 2);
+        let fmt = Corrected::new(format::GS8, &table);
+        let mut view = PixelView::new(Size::new(2, 1), (fmt, [0u8; 2]));
+
+        view.set(Point::new(0, 0), GS::new(200));
+
+        assert_eq!(view.get(Point::new(0, 0)), GS::new(200));
+
+        view.paint_rect(Rect::new(Point::new(0, 0), Size::new(2, 1)), &RGBA::new(0, 0, 0, 128));
+
+        assert_eq!(view.unwrap().1, [50, 0]);
+    }
+
+    #[test]
+    fn corrected_bits() {
+        let table = GammaTable::IDENTITY;
+
+        assert_eq!(Corrected::new(DynFormat::RGB565, &table).color_bits(), 16);
+        assert_eq!(Corrected::new(DynFormat::GS4, &table).color_bits(), 4);
+    }
+}
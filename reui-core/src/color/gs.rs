@@ -1,5 +1,7 @@
+use core::ops::Range;
+use core::marker::PhantomData;
 use crate::{ConstDefault};
-use super::{ColorFmt, ColorGet, ColorSet};
+use super::{ColorFmt, ColorGet, ColorSet, ColorAlpha, BitOrder, LSB, BW, ColorSpans, set_bits, set_bytes};
 
 /// Grayscale
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -20,21 +22,44 @@ impl From<u8> for GS {
     }
 }
 
-impl Into<u8> for GS {
-    fn into(self) -> u8 {
-        self.v
+impl From<GS> for u8 {
+    fn from(gs: GS) -> Self {
+        gs.v
+    }
+}
+
+impl From<BW> for GS {
+    fn from(bw: BW) -> Self {
+        Self::new(if bw.v { 255 } else { 0 })
+    }
+}
+
+impl From<GS> for BW {
+    fn from(gs: GS) -> Self {
+        BW { v: gs.v >= 128 }
+    }
+}
+
+impl ColorAlpha for GS {
+    fn alpha(&self) -> u8 {
+        255
     }
 }
 
 /// 1-bit grayscale color
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub struct GS1;
+pub struct GS1<Order = LSB> {
+    _order: PhantomData<Order>,
+}
 
-impl ConstDefault for GS1 {
-    const DEFAULT: Self = Self;
+/// GS1 format with LSB-first bit order
+pub const GS1: GS1 = GS1 { _order: PhantomData };
+
+impl<Order> ConstDefault for GS1<Order> {
+    const DEFAULT: Self = Self { _order: PhantomData };
 }
 
-impl ColorFmt for GS1 {
+impl<Order> ColorFmt for GS1<Order> {
     type ColorType = GS;
     type ColorBits = typenum::U1;
 
@@ -43,37 +68,49 @@ impl ColorFmt for GS1 {
     }
 }
 
-impl ColorGet for GS1 {
+impl<Order> GS1<Order> {
+    fn unpack(bits: u8) -> GS {
+        GS { v: if bits > 0 { 255 } else { 0 } }
+    }
+
+    fn pack(color: GS) -> u8 {
+        color.v >> 7
+    }
+}
+
+impl<Order: BitOrder> ColorGet for GS1<Order> {
     fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
-        let byte = buffer[index / 8];
-        let bit = 1u8 << (index % 8);
-        Self::ColorType {
-            v: if (byte & bit) > 0 { 255 } else { 0 }
-        }
+        Self::unpack(Order::get_bits(buffer, index, 1))
     }
 }
 
-impl ColorSet for GS1 {
+impl<Order: BitOrder> ColorSet for GS1<Order> {
     fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
-        let byte = &mut buffer[index / 8];
-        let bit = 1u8 << (index % 8);
-        if color.v > 127 {
-            *byte |= bit;
-        } else {
-            *byte &= !bit;
-        }
+        Order::set_bits(buffer, index, 1, Self::pack(color));
+    }
+
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        set_bits::<typenum::U1, Order, _>(buffer, ColorSpans::new(range, length, stride), colors, Self::pack);
     }
 }
 
 /// 2-bit grayscale color
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub struct GS2;
+pub struct GS2<Order = LSB> {
+    _order: PhantomData<Order>,
+}
 
-impl ConstDefault for GS2 {
-    const DEFAULT: Self = Self;
+/// GS2 format with LSB-first bit order
+pub const GS2: GS2 = GS2 { _order: PhantomData };
+
+impl<Order> ConstDefault for GS2<Order> {
+    const DEFAULT: Self = Self { _order: PhantomData };
 }
 
-impl ColorFmt for GS2 {
+impl<Order> ColorFmt for GS2<Order> {
     type ColorType = GS;
     type ColorBits = typenum::U2;
 
@@ -82,34 +119,49 @@ impl ColorFmt for GS2 {
     }
 }
 
-impl ColorGet for GS2 {
+impl<Order> GS2<Order> {
+    fn unpack(bits: u8) -> GS {
+        GS { v: bits * (255/3) }
+    }
+
+    fn pack(color: GS) -> u8 {
+        color.v >> 6
+    }
+}
+
+impl<Order: BitOrder> ColorGet for GS2<Order> {
     fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
-        let byte = buffer[index / 4];
-        let off = (index % 4) * 2;
-        Self::ColorType {
-            v: ((byte >> off) & 0b11) * (255/3)
-        }
+        Self::unpack(Order::get_bits(buffer, index, 2))
     }
 }
 
-impl ColorSet for GS2 {
+impl<Order: BitOrder> ColorSet for GS2<Order> {
     fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
-        let byte = &mut buffer[index / 4];
-        let off = (index % 4) * 2;
-        *byte &= !(0b11 << off);
-        *byte |= (color.v >> 6) << off;
+        Order::set_bits(buffer, index, 2, Self::pack(color));
+    }
+
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        set_bits::<typenum::U2, Order, _>(buffer, ColorSpans::new(range, length, stride), colors, Self::pack);
     }
 }
 
 /// 4-bit grayscale color
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub struct GS4;
+pub struct GS4<Order = LSB> {
+    _order: PhantomData<Order>,
+}
 
-impl ConstDefault for GS4 {
-    const DEFAULT: Self = Self;
+/// GS4 format with LSB-first bit order
+pub const GS4: GS4 = GS4 { _order: PhantomData };
+
+impl<Order> ConstDefault for GS4<Order> {
+    const DEFAULT: Self = Self { _order: PhantomData };
 }
 
-impl ColorFmt for GS4 {
+impl<Order> ColorFmt for GS4<Order> {
     type ColorType = GS;
     type ColorBits = typenum::U4;
 
@@ -118,22 +170,32 @@ impl ColorFmt for GS4 {
     }
 }
 
-impl ColorGet for GS4 {
+impl<Order> GS4<Order> {
+    fn unpack(bits: u8) -> GS {
+        GS { v: bits * (255/15) }
+    }
+
+    fn pack(color: GS) -> u8 {
+        color.v >> 4
+    }
+}
+
+impl<Order: BitOrder> ColorGet for GS4<Order> {
     fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
-        let byte = buffer[index / 2];
-        let off = (index % 2) * 4;
-        Self::ColorType {
-            v: ((byte >> off) & 0b1111) * (255/15)
-        }
+        Self::unpack(Order::get_bits(buffer, index, 4))
     }
 }
 
-impl ColorSet for GS4 {
+impl<Order: BitOrder> ColorSet for GS4<Order> {
     fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
-        let byte = &mut buffer[index / 2];
-        let off = (index % 2) * 4;
-        *byte &= !(0b1111 << off);
-        *byte |= (color.v >> 4) << off;
+        Order::set_bits(buffer, index, 4, Self::pack(color));
+    }
+
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        set_bits::<typenum::U4, Order, _>(buffer, ColorSpans::new(range, length, stride), colors, Self::pack);
     }
 }
 
@@ -154,21 +216,39 @@ impl ColorFmt for GS8 {
     }
 }
 
+impl GS8 {
+    fn unpack(bytes: &[u8]) -> GS {
+        GS { v: bytes[0] }
+    }
+
+    fn pack(GS { v }: GS, bytes: &mut [u8]) {
+        bytes[0] = v;
+    }
+}
+
 impl ColorGet for GS8 {
     fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
-        Self::ColorType { v: buffer[index] }
+        Self::unpack(&buffer[index..index + 1])
     }
 }
 
 impl ColorSet for GS8 {
     fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
-        buffer[index] = color.v;
+        Self::pack(color, &mut buffer[index..index + 1]);
+    }
+
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        set_bytes::<typenum::U1, _>(buffer, ColorSpans::new(range, length, stride), colors, Self::pack);
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::MSB;
 
     #[test]
     fn gs1_get() {
@@ -229,4 +309,40 @@ mod test {
 
         assert_eq!(&buf, &[12, 66]);
     }
+
+    #[test]
+    fn gs2_set_colors() {
+        let fmt = GS2::<MSB>::DEFAULT;
+        let mut buf = [0xff, 0xff, 0xff];
+
+        fmt.set_colors(&mut buf, 2..11, 9, 9, [0, 85, 170, 255, 0, 85, 170].iter().map(|v| GS::new(*v)));
+
+        assert_eq!(buf, [0b11_11_00_01, 0b10_11_00_01, 0b10_11_11_11]);
+    }
+
+    #[test]
+    fn gs2_msb() {
+        let fmt = GS2::<MSB>::DEFAULT;
+        let mut buf = [0b01_00_00_11, 0];
+
+        assert_eq!(fmt.get_color(&buf, 0), 85.into());
+        assert_eq!(fmt.get_color(&buf, 1), 0.into());
+        assert_eq!(fmt.get_color(&buf, 3), 255.into());
+
+        fmt.set_color(&mut buf, 1, 175.into());
+        fmt.set_color(&mut buf, 4, 255.into());
+
+        assert_eq!(&buf, &[0b01_10_00_11, 0b11_00_00_00]);
+    }
+
+    #[test]
+    fn gs2_colors() {
+        let mut buf = [0b11_10_01_00, 0b00_01_10_11];
+
+        assert!(GS2.get_colors(&buf, 1..8, 2, 3).eq([85, 170, 255, 170, 0].iter().map(|v| GS::new(*v))));
+
+        GS2.set_colors(&mut buf, 0..8, 1, 4, [GS::new(255), GS::new(0)].iter().cloned());
+
+        assert_eq!(&buf, &[0b11_10_01_11, 0b00_01_10_00]);
+    }
 }
@@ -1,5 +1,7 @@
+use core::ops::Range;
+use core::marker::PhantomData;
 use crate::{ConstDefault};
-use super::{ColorFmt, ColorGet, ColorSet, GS};
+use super::{ColorFmt, ColorGet, ColorSet, ColorAlpha, BitOrder, LSB, BW, ColorSpans, set_bits, set_bytes, GS};
 
 /// Grayscale with alpha
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -26,9 +28,9 @@ impl From<(u8, u8)> for GSA {
     }
 }
 
-impl Into<(u8, u8)> for GSA {
-    fn into(self) -> (u8, u8) {
-        (self.v, self.a)
+impl From<GSA> for (u8, u8) {
+    fn from(GSA { v, a }: GSA) -> Self {
+        (v, a)
     }
 }
 
@@ -38,9 +40,21 @@ impl From<u8> for GSA {
     }
 }
 
-impl Into<u8> for GSA {
-    fn into(self) -> u8 {
-        self.v
+impl From<GSA> for u8 {
+    fn from(gsa: GSA) -> Self {
+        gsa.v
+    }
+}
+
+impl From<BW> for GSA {
+    fn from(bw: BW) -> Self {
+        GS::from(bw).into()
+    }
+}
+
+impl From<GSA> for BW {
+    fn from(gsa: GSA) -> Self {
+        GS::from(gsa).into()
     }
 }
 
@@ -50,21 +64,32 @@ impl From<GS> for GSA {
     }
 }
 
-impl Into<GS> for GSA {
-    fn into(self) -> GS {
-        GS::new(self.into())
+impl From<GSA> for GS {
+    fn from(gsa: GSA) -> Self {
+        GS::new(gsa.v)
+    }
+}
+
+impl ColorAlpha for GSA {
+    fn alpha(&self) -> u8 {
+        self.a
     }
 }
 
 /// 1-bit grayscale color with 1-bit alpha channel
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub struct GSA11;
+pub struct GSA11<Order = LSB> {
+    _order: PhantomData<Order>,
+}
 
-impl ConstDefault for GSA11 {
-    const DEFAULT: Self = Self;
+/// GSA11 format with LSB-first bit order
+pub const GSA11: GSA11 = GSA11 { _order: PhantomData };
+
+impl<Order> ConstDefault for GSA11<Order> {
+    const DEFAULT: Self = Self { _order: PhantomData };
 }
 
-impl ColorFmt for GSA11 {
+impl<Order> ColorFmt for GSA11<Order> {
     type ColorType = GSA;
     type ColorBits = typenum::U2;
 
@@ -73,43 +98,52 @@ impl ColorFmt for GSA11 {
     }
 }
 
-impl ColorGet for GSA11 {
-    fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
-        let byte = buffer[index / 4];
-        let off = (index % 4) * 2;
-        Self::ColorType {
-            v: if (byte & (0b10 << off)) > 0 { 255 } else { 0 },
-            a: if (byte & (0b01 << off)) > 0 { 255 } else { 0 },
+impl<Order> GSA11<Order> {
+    fn unpack(bits: u8) -> GSA {
+        GSA {
+            v: if (bits & 0b10) > 0 { 255 } else { 0 },
+            a: if (bits & 0b01) > 0 { 255 } else { 0 },
         }
     }
+
+    fn pack(GSA { v, a }: GSA) -> u8 {
+        ((v >> 7) << 1) | (a >> 7)
+    }
 }
 
-impl ColorSet for GSA11 {
-    fn set_color(&self, buffer: &mut [u8], index: usize, GSA { v, a }: Self::ColorType) {
-        let byte = &mut buffer[index / 4];
-        let off = (index % 4) * 2;
-        if v > 127 {
-            *byte |= 0b10 << off;
-        } else {
-            *byte &= !(0b10 << off);
-        }
-        if a > 127 {
-            *byte |= 0b01 << off;
-        } else {
-            *byte &= !(0b01 << off);
-        }
+impl<Order: BitOrder> ColorGet for GSA11<Order> {
+    fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
+        Self::unpack(Order::get_bits(buffer, index, 2))
+    }
+}
+
+impl<Order: BitOrder> ColorSet for GSA11<Order> {
+    fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
+        Order::set_bits(buffer, index, 2, Self::pack(color));
+    }
+
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        set_bits::<typenum::U2, Order, _>(buffer, ColorSpans::new(range, length, stride), colors, Self::pack);
     }
 }
 
 /// 2-bit grayscale color with 2-bit alpha channel
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub struct GSA22;
+pub struct GSA22<Order = LSB> {
+    _order: PhantomData<Order>,
+}
 
-impl ConstDefault for GSA22 {
-    const DEFAULT: Self = Self;
+/// GSA22 format with LSB-first bit order
+pub const GSA22: GSA22 = GSA22 { _order: PhantomData };
+
+impl<Order> ConstDefault for GSA22<Order> {
+    const DEFAULT: Self = Self { _order: PhantomData };
 }
 
-impl ColorFmt for GSA22 {
+impl<Order> ColorFmt for GSA22<Order> {
     type ColorType = GSA;
     type ColorBits = typenum::U4;
 
@@ -118,35 +152,52 @@ impl ColorFmt for GSA22 {
     }
 }
 
-impl ColorGet for GSA22 {
-    fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
-        let byte = buffer[index / 2];
-        let off = (index % 2) * 4;
-        Self::ColorType {
-            v: ((byte >> (off + 1)) & 0b11) * (255/3),
-            a: ((byte >> off) & 0b11) * (255/3),
+impl<Order> GSA22<Order> {
+    fn unpack(bits: u8) -> GSA {
+        GSA {
+            v: (bits >> 2) * (255/3),
+            a: (bits & 0b11) * (255/3),
         }
     }
+
+    fn pack(GSA { v, a }: GSA) -> u8 {
+        ((v >> 6) << 2) | (a >> 6)
+    }
 }
 
-impl ColorSet for GSA22 {
-    fn set_color(&self, buffer: &mut [u8], index: usize, GSA { v, a }: Self::ColorType) {
-        let byte = &mut buffer[index / 2];
-        let off = (index % 2) * 4;
-        *byte &= !(0b1111 << off);
-        *byte |= (((v >> 6) << 2) | (a >> 6)) << off;
+impl<Order: BitOrder> ColorGet for GSA22<Order> {
+    fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
+        Self::unpack(Order::get_bits(buffer, index, 4))
+    }
+}
+
+impl<Order: BitOrder> ColorSet for GSA22<Order> {
+    fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
+        Order::set_bits(buffer, index, 4, Self::pack(color));
+    }
+
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        set_bits::<typenum::U4, Order, _>(buffer, ColorSpans::new(range, length, stride), colors, Self::pack);
     }
 }
 
 /// 3-bit grayscale color with 1-bit alpha channel
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub struct GSA31;
+pub struct GSA31<Order = LSB> {
+    _order: PhantomData<Order>,
+}
 
-impl ConstDefault for GSA31 {
-    const DEFAULT: Self = Self;
+/// GSA31 format with LSB-first bit order
+pub const GSA31: GSA31 = GSA31 { _order: PhantomData };
+
+impl<Order> ConstDefault for GSA31<Order> {
+    const DEFAULT: Self = Self { _order: PhantomData };
 }
 
-impl ColorFmt for GSA31 {
+impl<Order> ColorFmt for GSA31<Order> {
     type ColorType = GSA;
     type ColorBits = typenum::U4;
 
@@ -155,23 +206,35 @@ impl ColorFmt for GSA31 {
     }
 }
 
-impl ColorGet for GSA31 {
-    fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
-        let byte = buffer[index / 2];
-        let off = (index % 2) * 4;
-        Self::ColorType {
-            v: ((byte >> (off + 1)) & 0b111) * (255/7),
-            a: if (byte & (0b1 << off)) > 0 { 255 } else { 0 },
+impl<Order> GSA31<Order> {
+    fn unpack(bits: u8) -> GSA {
+        GSA {
+            v: (bits >> 1) * (255/7),
+            a: if (bits & 0b1) > 0 { 255 } else { 0 },
         }
     }
+
+    fn pack(GSA { v, a }: GSA) -> u8 {
+        ((v >> 5) << 1) | (a >> 7)
+    }
+}
+
+impl<Order: BitOrder> ColorGet for GSA31<Order> {
+    fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
+        Self::unpack(Order::get_bits(buffer, index, 4))
+    }
 }
 
-impl ColorSet for GSA31 {
-    fn set_color(&self, buffer: &mut [u8], index: usize, GSA { v, a }: Self::ColorType) {
-        let byte = &mut buffer[index / 2];
-        let off = (index % 2) * 4;
-        *byte &= !(0b1111 << off);
-        *byte |= (((v >> 5) << 1) | (a >> 7)) << off;
+impl<Order: BitOrder> ColorSet for GSA31<Order> {
+    fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
+        Order::set_bits(buffer, index, 4, Self::pack(color));
+    }
+
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        set_bits::<typenum::U4, Order, _>(buffer, ColorSpans::new(range, length, stride), colors, Self::pack);
     }
 }
 
@@ -192,18 +255,35 @@ impl ColorFmt for GSA44 {
     }
 }
 
+impl GSA44 {
+    fn unpack(bytes: &[u8]) -> GSA {
+        GSA {
+            v: (bytes[0] >> 4) * (255/15),
+            a: (bytes[0] & 0b1111) * (255/15),
+        }
+    }
+
+    fn pack(GSA { v, a }: GSA, bytes: &mut [u8]) {
+        bytes[0] = (v & (0b1111 << 4)) | (a >> 4);
+    }
+}
+
 impl ColorGet for GSA44 {
     fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
-        Self::ColorType {
-            v: (buffer[index] >> 4) * (255/15),
-            a: (buffer[index] & 0b1111) * (255/15),
-        }
+        Self::unpack(&buffer[index..index + 1])
     }
 }
 
 impl ColorSet for GSA44 {
-    fn set_color(&self, buffer: &mut [u8], index: usize, GSA { v, a }: Self::ColorType) {
-        buffer[index] = (v & (0b1111 << 4)) | (a >> 4);
+    fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
+        Self::pack(color, &mut buffer[index..index + 1]);
+    }
+
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        set_bytes::<typenum::U1, _>(buffer, ColorSpans::new(range, length, stride), colors, Self::pack);
     }
 }
 
@@ -224,18 +304,35 @@ impl ColorFmt for GSA71 {
     }
 }
 
+impl GSA71 {
+    fn unpack(bytes: &[u8]) -> GSA {
+        GSA {
+            v: (bytes[0] >> 1) * (255/127),
+            a: if (bytes[0] & 0b1) > 0 { 255 } else { 0 },
+        }
+    }
+
+    fn pack(GSA { v, a }: GSA, bytes: &mut [u8]) {
+        bytes[0] = (v & (0b1111111 << 1)) | (a >> (8 - 1));
+    }
+}
+
 impl ColorGet for GSA71 {
     fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
-        Self::ColorType {
-            v: (buffer[index] >> 1) * (255/127),
-            a: if (buffer[index] & 0b1) > 0 { 255 } else { 0 },
-        }
+        Self::unpack(&buffer[index..index + 1])
     }
 }
 
 impl ColorSet for GSA71 {
-    fn set_color(&self, buffer: &mut [u8], index: usize, GSA { v, a }: Self::ColorType) {
-        buffer[index] = (v & (0b1111111 << 1)) | (a >> (8 - 1));
+    fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
+        Self::pack(color, &mut buffer[index..index + 1]);
+    }
+
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        set_bytes::<typenum::U1, _>(buffer, ColorSpans::new(range, length, stride), colors, Self::pack);
     }
 }
 
@@ -256,20 +353,37 @@ impl ColorFmt for GSA88 {
     }
 }
 
+impl GSA88 {
+    fn unpack(bytes: &[u8]) -> GSA {
+        GSA {
+            v: bytes[0],
+            a: bytes[1],
+        }
+    }
+
+    fn pack(GSA { v, a }: GSA, bytes: &mut [u8]) {
+        bytes[0] = v;
+        bytes[1] = a;
+    }
+}
+
 impl ColorGet for GSA88 {
     fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
         let i = index * 2;
-        Self::ColorType {
-            v: buffer[i],
-            a: buffer[i + 1],
-        }
+        Self::unpack(&buffer[i..i + 2])
     }
 }
 
 impl ColorSet for GSA88 {
-    fn set_color(&self, buffer: &mut [u8], index: usize, GSA { v, a }: Self::ColorType) {
+    fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
         let i = index * 2;
-        buffer[i] = v;
-        buffer[i + 1] = a;
+        Self::pack(color, &mut buffer[i..i + 2]);
+    }
+
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        set_bytes::<typenum::U2, _>(buffer, ColorSpans::new(range, length, stride), colors, Self::pack);
     }
 }
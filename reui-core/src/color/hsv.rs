@@ -0,0 +1,297 @@
+use crate::{Paint, Point};
+use super::{ColorAlpha, RGB, RGBA, lerp, mul_div255};
+
+/// Hue, saturation and value
+///
+/// Hue is in degrees from 0 to 359, saturation and value is fractions of 255.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HSV {
+    pub h: u16,
+    pub s: u8,
+    pub v: u8,
+}
+
+impl HSV {
+    /// Create HSV color from components
+    ///
+    /// Hue wraps around 360 degrees.
+    pub fn new(h: u16, s: u8, v: u8) -> Self {
+        Self { h: h % 360, s, v }
+    }
+
+    /// Mix with other color using fraction of 255
+    ///
+    /// Hue goes along shortest arc of color circle.
+    pub fn mix(self, other: Self, t: u8) -> Self {
+        Self { h: mix_hue(self.h, other.h, t), s: lerp(self.s, other.s, t), v: lerp(self.v, other.v, t) }
+    }
+
+    /// Increase value by amount
+    pub fn lighten(self, amount: u8) -> Self {
+        Self { v: self.v.saturating_add(amount), ..self }
+    }
+
+    /// Decrease value by amount
+    pub fn darken(self, amount: u8) -> Self {
+        Self { v: self.v.saturating_sub(amount), ..self }
+    }
+}
+
+/// Hue, saturation and lightness
+///
+/// Hue is in degrees from 0 to 359, saturation and lightness is fractions of 255.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HSL {
+    pub h: u16,
+    pub s: u8,
+    pub l: u8,
+}
+
+impl HSL {
+    /// Create HSL color from components
+    ///
+    /// Hue wraps around 360 degrees.
+    pub fn new(h: u16, s: u8, l: u8) -> Self {
+        Self { h: h % 360, s, l }
+    }
+
+    /// Mix with other color using fraction of 255
+    ///
+    /// Hue goes along shortest arc of color circle.
+    pub fn mix(self, other: Self, t: u8) -> Self {
+        Self { h: mix_hue(self.h, other.h, t), s: lerp(self.s, other.s, t), l: lerp(self.l, other.l, t) }
+    }
+
+    /// Increase lightness by amount
+    pub fn lighten(self, amount: u8) -> Self {
+        Self { l: self.l.saturating_add(amount), ..self }
+    }
+
+    /// Decrease lightness by amount
+    pub fn darken(self, amount: u8) -> Self {
+        Self { l: self.l.saturating_sub(amount), ..self }
+    }
+}
+
+/// Interpolate hues along shortest arc using fraction of 255
+fn mix_hue(a: u16, b: u16, t: u8) -> u16 {
+    let d = (b as i32 - a as i32 + 540) % 360 - 180;
+    (a as i32 + (2 * d * t as i32 + 255).div_euclid(510)).rem_euclid(360) as u16
+}
+
+/// Build RGB color from hue, chroma and minimum component
+fn hue_to_rgb(h: u16, c: u8, m: u8) -> RGB {
+    let f = ((h % 120) as u32 * 255 + 30) / 60;
+    let x = mul_div255(c, (255 - (f as i32 - 255).abs()) as u8);
+
+    let (r, g, b) = match h / 60 {
+        0 => (c, x, 0),
+        1 => (x, c, 0),
+        2 => (0, c, x),
+        3 => (0, x, c),
+        4 => (x, 0, c),
+        _ => (c, 0, x),
+    };
+
+    RGB::new(r + m, g + m, b + m)
+}
+
+/// Get hue of RGB color in degrees with maximum component and chroma
+fn rgb_to_hue(RGB { r, g, b }: RGB, max: u8, c: u8) -> u16 {
+    if c == 0 {
+        return 0;
+    }
+
+    let (r, g, b, c) = (r as i32, g as i32, b as i32, c as i32);
+
+    let x = if max as i32 == r {
+        60 * (g - b)
+    } else if max as i32 == g {
+        120 * c + 60 * (b - r)
+    } else {
+        240 * c + 60 * (r - g)
+    };
+
+    ((2 * x + c).div_euclid(2 * c)).rem_euclid(360) as u16
+}
+
+impl From<HSV> for RGB {
+    fn from(HSV { h, s, v }: HSV) -> Self {
+        let c = mul_div255(v, s);
+        hue_to_rgb(h % 360, c, v - c)
+    }
+}
+
+impl From<RGB> for HSV {
+    fn from(rgb: RGB) -> Self {
+        let max = rgb.r.max(rgb.g).max(rgb.b);
+        let min = rgb.r.min(rgb.g).min(rgb.b);
+        let c = max - min;
+
+        let s = if max == 0 { 0 } else { ((c as u16 * 255 + max as u16 / 2) / max as u16) as u8 };
+
+        Self { h: rgb_to_hue(rgb, max, c), s, v: max }
+    }
+}
+
+impl From<HSL> for RGB {
+    fn from(HSL { h, s, l }: HSL) -> Self {
+        let c = mul_div255((255 - (2 * l as i16 - 255).abs()) as u8, s);
+        hue_to_rgb(h % 360, c, l - c.div_ceil(2))
+    }
+}
+
+impl From<RGB> for HSL {
+    fn from(rgb: RGB) -> Self {
+        let max = rgb.r.max(rgb.g).max(rgb.b);
+        let min = rgb.r.min(rgb.g).min(rgb.b);
+        let c = max - min;
+        let sum = max as u16 + min as u16;
+
+        let d = 255 - (sum as i32 - 255).abs();
+        let s = if c == 0 { 0 } else { ((c as i32 * 255 + d / 2) / d).min(255) as u8 };
+
+        Self { h: rgb_to_hue(rgb, max, c), s, l: sum.div_ceil(2) as u8 }
+    }
+}
+
+impl From<HSV> for HSL {
+    fn from(hsv: HSV) -> Self {
+        RGB::from(hsv).into()
+    }
+}
+
+impl From<HSL> for HSV {
+    fn from(hsl: HSL) -> Self {
+        RGB::from(hsl).into()
+    }
+}
+
+impl From<HSV> for RGBA {
+    fn from(hsv: HSV) -> Self {
+        RGB::from(hsv).into()
+    }
+}
+
+impl From<RGBA> for HSV {
+    fn from(rgba: RGBA) -> Self {
+        RGB::from(rgba).into()
+    }
+}
+
+impl From<HSL> for RGBA {
+    fn from(hsl: HSL) -> Self {
+        RGB::from(hsl).into()
+    }
+}
+
+impl From<RGBA> for HSL {
+    fn from(rgba: RGBA) -> Self {
+        RGB::from(rgba).into()
+    }
+}
+
+impl ColorAlpha for HSV {
+    fn alpha(&self) -> u8 {
+        255
+    }
+}
+
+impl ColorAlpha for HSL {
+    fn alpha(&self) -> u8 {
+        255
+    }
+}
+
+impl Paint for HSV {
+    fn color_at(&self, _point: Point<i32>) -> RGBA {
+        (*self).into()
+    }
+}
+
+impl Paint for HSL {
+    fn color_at(&self, _point: Point<i32>) -> RGBA {
+        (*self).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{format, ColorConvert, GS, PixelView, Rect, Size};
+
+    #[test]
+    fn hsv() {
+        assert_eq!(RGB::from(HSV::new(0, 255, 255)), RGB::new(255, 0, 0));
+        assert_eq!(RGB::from(HSV::new(60, 255, 255)), RGB::new(255, 255, 0));
+        assert_eq!(RGB::from(HSV::new(120, 255, 255)), RGB::new(0, 255, 0));
+        assert_eq!(RGB::from(HSV::new(240, 255, 128)), RGB::new(0, 0, 128));
+        assert_eq!(RGB::from(HSV::new(30, 255, 255)), RGB::new(255, 128, 0));
+        assert_eq!(RGB::from(HSV::new(400, 0, 100)), RGB::new(100, 100, 100));
+
+        assert_eq!(HSV::from(RGB::new(255, 0, 0)), HSV::new(0, 255, 255));
+        assert_eq!(HSV::from(RGB::new(0, 0, 128)), HSV::new(240, 255, 128));
+        assert_eq!(HSV::from(RGB::new(255, 0, 128)), HSV::new(330, 255, 255));
+        assert_eq!(HSV::from(RGB::new(50, 50, 50)), HSV::new(0, 0, 50));
+    }
+
+    #[test]
+    fn hsl() {
+        assert_eq!(RGB::from(HSL::new(0, 255, 128)), RGB::new(255, 1, 1));
+        assert_eq!(RGB::from(HSL::new(120, 255, 255)), RGB::new(255, 255, 255));
+        assert_eq!(RGB::from(HSL::new(180, 0, 77)), RGB::new(77, 77, 77));
+        assert_eq!(RGB::from(HSL::new(300, 255, 64)), RGB::new(128, 0, 128));
+
+        assert_eq!(HSL::from(RGB::new(255, 0, 0)), HSL::new(0, 255, 128));
+        assert_eq!(HSL::from(RGB::new(128, 0, 128)), HSL::new(300, 255, 64));
+        assert_eq!(HSL::from(RGB::new(0, 0, 0)), HSL::new(0, 0, 0));
+
+        assert_eq!(HSL::new(10, 20, 250).lighten(10), HSL::new(10, 20, 255));
+        assert_eq!(HSL::new(10, 20, 30).darken(10), HSL::new(10, 20, 20));
+        assert_eq!(HSL::new(100, 0, 0).mix(HSL::new(200, 255, 255), 128), HSL::new(150, 128, 128));
+    }
+
+    #[test]
+    fn hsv_helpers() {
+        assert_eq!(HSV::new(10, 20, 250).lighten(10), HSV::new(10, 20, 255));
+        assert_eq!(HSV::new(10, 20, 30).darken(40), HSV::new(10, 20, 0));
+        assert_eq!(HSV::new(350, 0, 0).mix(HSV::new(10, 255, 255), 128), HSV::new(0, 128, 128));
+        assert_eq!(HSV::new(10, 0, 0).mix(HSV::new(350, 0, 0), 128), HSV::new(0, 0, 0));
+        assert_eq!(HSV::new(300, 0, 0).mix(HSV::new(20, 0, 0), 255), HSV::new(20, 0, 0));
+        assert_eq!(HSV::new(300, 0, 0).mix(HSV::new(20, 0, 0), 0), HSV::new(300, 0, 0));
+    }
+
+    #[test]
+    fn paint() {
+        let mut view = PixelView::new(Size::new(2, 1), (format::RGB888, [0u8; 6]));
+
+        view.paint_rect(Rect::new(Point::new(0, 0), Size::new(1, 1)), &HSV::new(120, 255, 255));
+        view.paint_rect(Rect::new(Point::new(1, 0), Size::new(1, 1)), &HSL::new(240, 255, 128));
+
+        assert_eq!(view.unwrap().1, [0, 255, 0, 1, 1, 255]);
+    }
+
+    #[test]
+    fn round_trip() {
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(17) {
+                for b in (0..=255).step_by(51) {
+                    let rgb = RGB::new(r, g, b);
+
+                    for back in &[RGB::from(HSV::from(rgb)), RGB::from(HSL::from(rgb))] {
+                        assert!((back.r as i16 - r as i16).abs() <= 3, "{:?} {:?}", rgb, back);
+                        assert!((back.g as i16 - g as i16).abs() <= 3, "{:?} {:?}", rgb, back);
+                        assert!((back.b as i16 - b as i16).abs() <= 3, "{:?} {:?}", rgb, back);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn convert() {
+        assert_eq!(HSV::new(0, 0, 255).convert::<GS>(), GS::new(255));
+        assert_eq!(RGBA::from(HSL::new(0, 255, 128)).a, 255);
+        assert_eq!(HSL::from(HSV::new(120, 255, 255)), HSL::new(120, 255, 128));
+    }
+}
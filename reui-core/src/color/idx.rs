@@ -1,19 +1,29 @@
-use super::{ColorFmt, ColorGet, ColorSet, ColorBuf};
+use core::ops::Range;
+use core::marker::PhantomData;
+use super::{ColorFmt, ColorGet, ColorSet, ColorBuf, BitOrder, LSB, ColorSpans, set_bits, PaletteLookup, NearestColor};
 
 /// 1-bit indexed color format
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-#[repr(transparent)]
-pub struct IDX1<Buf> {
+pub struct IDX1<Buf, Order = LSB, Lookup = NearestColor> {
     colors: Buf,
+    lookup: Lookup,
+    _order: PhantomData<Order>,
 }
 
-impl<Buf> IDX1<Buf> {
+impl<Buf, Order> IDX1<Buf, Order> {
     pub const fn new(colors: Buf) -> Self {
-        Self { colors }
+        Self { colors, lookup: NearestColor, _order: PhantomData }
     }
 }
 
-impl<Buf> ColorFmt for IDX1<Buf>
+impl<Buf, Order, Lookup> IDX1<Buf, Order, Lookup> {
+    /// Use another palette lookup method
+    pub fn with_lookup<NewLookup>(self, lookup: NewLookup) -> IDX1<Buf, Order, NewLookup> {
+        IDX1 { colors: self.colors, lookup, _order: PhantomData }
+    }
+}
+
+impl<Buf, Order, Lookup> ColorFmt for IDX1<Buf, Order, Lookup>
 where
     Buf: ColorBuf,
 {
@@ -25,54 +35,68 @@ where
     }
 }
 
-impl<Buf> ColorGet for IDX1<Buf>
+impl<Buf, Order, Lookup> ColorGet for IDX1<Buf, Order, Lookup>
 where
     Buf: ColorBuf,
+    Order: BitOrder,
 {
     fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
-        let color_index = ((buffer[index / 8] >> (index % 8)) & 0b1) as usize;
+        let color_index = Order::get_bits(buffer, index, 1) as usize;
 
         self.colors.get(color_index)
     }
 }
 
-impl<Buf> ColorSet for IDX1<Buf>
+impl<Buf, Order, Lookup> IDX1<Buf, Order, Lookup>
 where
     Buf: ColorBuf,
-    <Buf::ColorFmt as ColorFmt>::ColorType: PartialEq,
+    Lookup: PaletteLookup<<Buf::ColorFmt as ColorFmt>::ColorType>,
+{
+    fn pack(&self, color: &<Buf::ColorFmt as ColorFmt>::ColorType) -> u8 {
+        self.lookup.lookup(&self.colors, 2, color) as u8
+    }
+}
+
+impl<Buf, Order, Lookup> ColorSet for IDX1<Buf, Order, Lookup>
+where
+    Buf: ColorBuf,
+    Order: BitOrder,
+    Lookup: PaletteLookup<Self::ColorType>,
 {
     fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
-        (0usize..2).into_iter().filter_map(|color_index| {
-            if self.colors.get(color_index) == color {
-                Some(color_index)
-            } else {
-                None
-            }
-        }).next().map(|color_index| {
-            let cell = &mut buffer[index / 8];
-            let bit = 1u8 << (index % 8);
-            if color_index > 0 {
-                *cell |= bit;
-            } else {
-                *cell &= !bit;
-            }
-        });
+        Order::set_bits(buffer, index, 1, self.pack(&color));
+    }
+
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        set_bits::<typenum::U1, Order, _>(buffer, ColorSpans::new(range, length, stride), colors, |color| self.pack(&color));
     }
 }
 
 /// 2-bit indexed color format
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub struct IDX2<Buf> {
+pub struct IDX2<Buf, Order = LSB, Lookup = NearestColor> {
     colors: Buf,
+    lookup: Lookup,
+    _order: PhantomData<Order>,
 }
 
-impl<Buf> IDX2<Buf> {
+impl<Buf, Order> IDX2<Buf, Order> {
     pub const fn new(colors: Buf) -> Self {
-        Self { colors }
+        Self { colors, lookup: NearestColor, _order: PhantomData }
     }
 }
 
-impl<Buf> ColorFmt for IDX2<Buf>
+impl<Buf, Order, Lookup> IDX2<Buf, Order, Lookup> {
+    /// Use another palette lookup method
+    pub fn with_lookup<NewLookup>(self, lookup: NewLookup) -> IDX2<Buf, Order, NewLookup> {
+        IDX2 { colors: self.colors, lookup, _order: PhantomData }
+    }
+}
+
+impl<Buf, Order, Lookup> ColorFmt for IDX2<Buf, Order, Lookup>
 where
     Buf: ColorBuf,
 {
@@ -84,53 +108,68 @@ where
     }
 }
 
-impl<Buf> ColorGet for IDX2<Buf>
+impl<Buf, Order, Lookup> ColorGet for IDX2<Buf, Order, Lookup>
 where
     Buf: ColorBuf,
+    Order: BitOrder,
 {
     fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
-        let byte = buffer[index / 4];
-        let off = (index % 4) * 2;
-        let color_index = ((byte >> off) & 0b11) as usize;
+        let color_index = Order::get_bits(buffer, index, 2) as usize;
 
         self.colors.get(color_index)
     }
 }
 
-impl<Buf> ColorSet for IDX2<Buf>
+impl<Buf, Order, Lookup> IDX2<Buf, Order, Lookup>
+where
+    Buf: ColorBuf,
+    Lookup: PaletteLookup<<Buf::ColorFmt as ColorFmt>::ColorType>,
+{
+    fn pack(&self, color: &<Buf::ColorFmt as ColorFmt>::ColorType) -> u8 {
+        self.lookup.lookup(&self.colors, 4, color) as u8
+    }
+}
+
+impl<Buf, Order, Lookup> ColorSet for IDX2<Buf, Order, Lookup>
 where
     Buf: ColorBuf,
-    <Buf::ColorFmt as ColorFmt>::ColorType: PartialEq,
+    Order: BitOrder,
+    Lookup: PaletteLookup<Self::ColorType>,
 {
     fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
-        (0usize..4).into_iter().filter_map(|color_index| {
-            if self.colors.get(color_index) == color {
-                Some(color_index as u8)
-            } else {
-                None
-            }
-        }).next().map(|color_index| {
-            let byte = &mut buffer[index / 4];
-            let off = (index % 4) * 2;
-            *byte &= !(0b11 << off);
-            *byte |= (color_index >> 6) << off;
-        });
+        Order::set_bits(buffer, index, 2, self.pack(&color));
+    }
+
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        set_bits::<typenum::U2, Order, _>(buffer, ColorSpans::new(range, length, stride), colors, |color| self.pack(&color));
     }
 }
 
 /// 4-bit indexed color format
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub struct IDX4<Buf> {
+pub struct IDX4<Buf, Order = LSB, Lookup = NearestColor> {
     colors: Buf,
+    lookup: Lookup,
+    _order: PhantomData<Order>,
 }
 
-impl<Buf> IDX4<Buf> {
+impl<Buf, Order> IDX4<Buf, Order> {
     pub const fn new(colors: Buf) -> Self {
-        Self { colors }
+        Self { colors, lookup: NearestColor, _order: PhantomData }
     }
 }
 
-impl<Buf> ColorFmt for IDX4<Buf>
+impl<Buf, Order, Lookup> IDX4<Buf, Order, Lookup> {
+    /// Use another palette lookup method
+    pub fn with_lookup<NewLookup>(self, lookup: NewLookup) -> IDX4<Buf, Order, NewLookup> {
+        IDX4 { colors: self.colors, lookup, _order: PhantomData }
+    }
+}
+
+impl<Buf, Order, Lookup> ColorFmt for IDX4<Buf, Order, Lookup>
 where
     Buf: ColorBuf,
 {
@@ -142,53 +181,67 @@ where
     }
 }
 
-impl<Buf> ColorGet for IDX4<Buf>
+impl<Buf, Order, Lookup> ColorGet for IDX4<Buf, Order, Lookup>
 where
     Buf: ColorBuf,
+    Order: BitOrder,
 {
     fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
-        let byte = buffer[index / 2];
-        let off = (index % 2) * 4;
-        let color_index = ((byte >> off) & 0b1111) as usize;
+        let color_index = Order::get_bits(buffer, index, 4) as usize;
 
         self.colors.get(color_index)
     }
 }
 
-impl<Buf> ColorSet for IDX4<Buf>
+impl<Buf, Order, Lookup> IDX4<Buf, Order, Lookup>
 where
     Buf: ColorBuf,
-    <Buf::ColorFmt as ColorFmt>::ColorType: PartialEq,
+    Lookup: PaletteLookup<<Buf::ColorFmt as ColorFmt>::ColorType>,
+{
+    fn pack(&self, color: &<Buf::ColorFmt as ColorFmt>::ColorType) -> u8 {
+        self.lookup.lookup(&self.colors, 16, color) as u8
+    }
+}
+
+impl<Buf, Order, Lookup> ColorSet for IDX4<Buf, Order, Lookup>
+where
+    Buf: ColorBuf,
+    Order: BitOrder,
+    Lookup: PaletteLookup<Self::ColorType>,
 {
     fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
-        (0usize..16).into_iter().filter_map(|color_index| {
-            if self.colors.get(color_index) == color {
-                Some(color_index as u8)
-            } else {
-                None
-            }
-        }).next().map(|color_index| {
-            let byte = &mut buffer[index / 2];
-            let off = (index % 2) * 4;
-            *byte &= !(0b1111 << off);
-            *byte |= (color_index >> 6) << off;
-        });
+        Order::set_bits(buffer, index, 4, self.pack(&color));
+    }
+
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        set_bits::<typenum::U4, Order, _>(buffer, ColorSpans::new(range, length, stride), colors, |color| self.pack(&color));
     }
 }
 
 /// 8-bit indexed color format
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub struct IDX8<Buf> {
+pub struct IDX8<Buf, Lookup = NearestColor> {
     colors: Buf,
+    lookup: Lookup,
 }
 
 impl<Buf> IDX8<Buf> {
     pub const fn new(colors: Buf) -> Self {
-        Self { colors }
+        Self { colors, lookup: NearestColor }
+    }
+}
+
+impl<Buf, Lookup> IDX8<Buf, Lookup> {
+    /// Use another palette lookup method
+    pub fn with_lookup<NewLookup>(self, lookup: NewLookup) -> IDX8<Buf, NewLookup> {
+        IDX8 { colors: self.colors, lookup }
     }
 }
 
-impl<Buf> ColorFmt for IDX8<Buf>
+impl<Buf, Lookup> ColorFmt for IDX8<Buf, Lookup>
 where
     Buf: ColorBuf,
 {
@@ -200,7 +253,7 @@ where
     }
 }
 
-impl<Buf> ColorGet for IDX8<Buf>
+impl<Buf, Lookup> ColorGet for IDX8<Buf, Lookup>
 where
     Buf: ColorBuf,
 {
@@ -211,20 +264,79 @@ where
     }
 }
 
-impl<Buf> ColorSet for IDX8<Buf>
+impl<Buf, Lookup> ColorSet for IDX8<Buf, Lookup>
 where
     Buf: ColorBuf,
-    <Buf::ColorFmt as ColorFmt>::ColorType: PartialEq,
+    Lookup: PaletteLookup<Self::ColorType>,
 {
     fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
-        (0usize..256).into_iter().filter_map(|color_index| {
-            if self.colors.get(color_index) == color {
-                Some(color_index as u8)
-            } else {
-                None
-            }
-        }).next().map(|color_index| {
-            buffer[index] = color_index;
-        });
+        buffer[index] = self.lookup.lookup(&self.colors, 256, &color) as u8;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{format, RGB, ReverseLut};
+
+    const PALETTE: [u8; 12] = [0, 0, 0, 255, 255, 255, 255, 0, 0, 0, 0, 128];
+
+    #[test]
+    fn nearest() {
+        let fmt = <IDX2<_>>::new((format::RGB888, PALETTE));
+        let mut buffer = [0u8; 1];
+
+        fmt.set_color(&mut buffer, 0, RGB::new(250, 10, 0));
+        fmt.set_color(&mut buffer, 1, RGB::new(0, 0, 100));
+        fmt.set_color(&mut buffer, 2, RGB::new(230, 230, 230));
+
+        assert_eq!(buffer, [0b0001_1110]);
+        assert_eq!(fmt.get_color(&buffer, 0), RGB::new(255, 0, 0));
+    }
+
+    #[test]
+    fn set_colors() {
+        let fmt = <IDX2<_>>::new((format::RGB888, PALETTE));
+        let mut buffer = [0u8; 2];
+
+        fmt.set_colors(&mut buffer, 1..6, 5, 5, [RGB::new(250, 10, 0), RGB::new(0, 0, 100), RGB::new(230, 230, 230)].iter().cloned());
+
+        assert_eq!(buffer, [0b01_11_10_00, 0]);
+    }
+
+    #[test]
+    fn short_palette() {
+        let fmt = IDX8::new((format::RGB888, PALETTE));
+        let mut buffer = [7u8; 2];
+
+        fmt.set_color(&mut buffer, 0, RGB::new(255, 255, 200));
+        fmt.set_color(&mut buffer, 1, RGB::new(20, 20, 20));
+
+        assert_eq!(buffer, [1, 0]);
+    }
+
+    #[test]
+    fn reverse_lookup() {
+        let palette = (format::RGB888, PALETTE);
+        let lut = ReverseLut::new(&palette, 4);
+        let fmt = <IDX4<_>>::new(palette).with_lookup(&lut);
+        let mut buffer = [0u8; 1];
+
+        fmt.set_color(&mut buffer, 0, RGB::new(0, 0, 140));
+        fmt.set_color(&mut buffer, 1, RGB::new(240, 16, 16));
+
+        assert_eq!(buffer, [0x23]);
+    }
+
+    #[test]
+    fn raw_palette() {
+        let fmt = <IDX2<_>>::new((format::RAW8, [0u8, 10, 20, 30]));
+        let mut buffer = [0u8; 1];
+
+        fmt.set_color(&mut buffer, 0, 20);
+        fmt.set_color(&mut buffer, 1, 12);
+
+        assert_eq!(buffer, [0b0000_0110]);
+        assert_eq!(fmt.get_color(&buffer, 0), 20);
     }
 }
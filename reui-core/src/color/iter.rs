@@ -0,0 +1,196 @@
+use core::ops::Range;
+use typenum::Unsigned;
+use super::{ColorGet, BitOrder};
+
+/// Spans of color indexes
+///
+/// Splits range of colors to pieces of specified length with specified stride.
+/// When stride is not greater than length the range is treated as contiguous.
+#[derive(Debug, Clone)]
+pub struct ColorSpans {
+    start: usize,
+    end: usize,
+    length: usize,
+    stride: usize,
+}
+
+impl ColorSpans {
+    pub fn new(range: Range<usize>, length: usize, stride: usize) -> Self {
+        let (length, stride) = if stride <= length {
+            let length = range.end.saturating_sub(range.start);
+            (length, length.max(1))
+        } else {
+            (length, stride)
+        };
+
+        Self { start: range.start, end: range.end, length, stride }
+    }
+}
+
+impl Iterator for ColorSpans {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start >= self.end || self.length == 0 {
+            return None;
+        }
+
+        let start = self.start;
+        self.start += self.stride;
+
+        Some(start..(start + self.length).min(self.end))
+    }
+}
+
+/// Generic iterator over colors
+///
+/// Gets colors one by one using format.
+pub struct ColorsIter<'a, Fmt> {
+    format: &'a Fmt,
+    buffer: &'a [u8],
+    spans: ColorSpans,
+    piece: Range<usize>,
+}
+
+impl<'a, Fmt> ColorsIter<'a, Fmt> {
+    pub fn new(format: &'a Fmt, buffer: &'a [u8], spans: ColorSpans) -> Self {
+        Self { format, buffer, spans, piece: 0..0 }
+    }
+}
+
+impl<'a, Fmt> Iterator for ColorsIter<'a, Fmt>
+where
+    Fmt: ColorGet,
+{
+    type Item = Fmt::ColorType;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(index) = self.piece.next() {
+                return Some(self.format.get_color(self.buffer, index));
+            }
+            self.piece = self.spans.next()?;
+        }
+    }
+}
+
+/// Set colors which packs into whole number of bytes
+pub(crate) fn set_bytes<Bytes, Color>(
+    buffer: &mut [u8],
+    spans: ColorSpans,
+    colors: impl IntoIterator<Item = Color>,
+    pack: impl Fn(Color, &mut [u8]),
+) where
+    Bytes: Unsigned,
+{
+    let mut colors = colors.into_iter();
+
+    for piece in spans {
+        let bytes = &mut buffer[piece.start * Bytes::USIZE..piece.end * Bytes::USIZE];
+
+        for (bytes, color) in bytes.chunks_exact_mut(Bytes::USIZE).zip(&mut colors) {
+            pack(color, bytes);
+        }
+    }
+}
+
+/// Set colors which packs several into single byte
+///
+/// The bytes which fully covered by colors are assembled and written at once.
+pub(crate) fn set_bits<Bits, Order, Color>(
+    buffer: &mut [u8],
+    spans: ColorSpans,
+    colors: impl IntoIterator<Item = Color>,
+    pack: impl Fn(Color) -> u8,
+) where
+    Bits: Unsigned,
+    Order: BitOrder,
+{
+    let bits = Bits::USIZE;
+    let cpb = 8 / bits;
+    let mask = !0u8 >> (8 - bits);
+    let mut colors = colors.into_iter();
+
+    for piece in spans {
+        let mut index = piece.start;
+
+        while index < piece.end {
+            // colors which belongs to current byte
+            let start = index % cpb;
+            let end = (start + piece.end - index).min(cpb);
+            let mut value = 0u8;
+            let mut written = 0u8;
+
+            for offset in start..end {
+                let color = match colors.next() {
+                    Some(color) => color,
+                    None => break,
+                };
+                let off = Order::bit_offset(offset, bits);
+
+                value |= (pack(color) & mask) << off;
+                written |= mask << off;
+            }
+
+            let byte = &mut buffer[index / cpb];
+            *byte = (*byte & !written) | value;
+
+            if written.count_ones() as usize != (end - start) * bits {
+                return;
+            }
+
+            index += end - start;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use typenum::{U2, U4};
+    use crate::{format, ConstDefault, LSB, MSB};
+
+    #[test]
+    fn spans() {
+        assert!(ColorSpans::new(2..12, 3, 4).eq([2..5, 6..9, 10..12].iter().cloned()));
+        assert!(ColorSpans::new(2..12, 4, 4).eq(core::iter::once(2..12)));
+        assert_eq!(ColorSpans::new(2..12, 0, 4).count(), 0);
+        assert_eq!(ColorSpans::new(2..2, 3, 4).count(), 0);
+    }
+
+    #[test]
+    fn colors() {
+        let buf = [0b11_10_01_00, 0b00_01_10_11];
+        let fmt = <format::RAW2>::DEFAULT;
+
+        assert!(ColorsIter::new(&fmt, &buf, ColorSpans::new(1..8, 2, 3)).eq([1, 2, 3, 2, 0].iter().cloned()));
+
+        let fmt = format::RAW4::<MSB>::DEFAULT;
+
+        assert!(ColorsIter::new(&fmt, &buf, ColorSpans::new(0..4, 4, 4)).eq([14, 4, 1, 11].iter().cloned()));
+    }
+
+    #[test]
+    fn set_packed() {
+        let mut buf = [0xffu8; 4];
+
+        set_bits::<U2, LSB, _>(&mut buf, ColorSpans::new(3..16, 6, 8), [0u8, 1, 2, 3, 0, 1, 2, 3, 0, 1], |c| c);
+
+        assert_eq!(buf, [0b00_11_11_11, 0b00_11_10_01, 0b10_11_11_01, 0b11_01_00_11]);
+
+        let mut buf = [0u8; 2];
+
+        set_bits::<U4, MSB, _>(&mut buf, ColorSpans::new(0..4, 4, 4), [1u8, 2, 3], |c| c);
+
+        assert_eq!(buf, [0x12, 0x30]);
+    }
+
+    #[test]
+    fn set() {
+        let mut buf = [0u8; 8];
+
+        set_bytes::<U2, _>(&mut buf, ColorSpans::new(1..4, 1, 2), [1u8, 2, 3], |c, bs| bs.copy_from_slice(&[c, c]));
+
+        assert_eq!(buf, [0, 0, 1, 1, 0, 0, 2, 2]);
+    }
+}
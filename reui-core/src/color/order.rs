@@ -0,0 +1,102 @@
+/// Order of bits for sub-byte color formats
+///
+/// Formats which packs several colors into single byte uses bit order
+/// to determine which bits of byte belongs to color with specified index.
+pub trait BitOrder {
+    /// Get offset in bits of color with specified index in the byte
+    fn bit_offset(index: usize, bits: usize) -> usize;
+
+    /// Get color bits which located at specified index
+    fn get_bits(buffer: &[u8], index: usize, bits: usize) -> u8 {
+        let cpb = 8 / bits;
+        let off = Self::bit_offset(index % cpb, bits);
+        (buffer[index / cpb] >> off) & (!0u8 >> (8 - bits))
+    }
+
+    /// Set color bits which located at specified index
+    fn set_bits(buffer: &mut [u8], index: usize, bits: usize, value: u8) {
+        let cpb = 8 / bits;
+        let off = Self::bit_offset(index % cpb, bits);
+        let msk = !0u8 >> (8 - bits);
+        let byte = &mut buffer[index / cpb];
+        *byte &= !(msk << off);
+        *byte |= (value & msk) << off;
+    }
+}
+
+/// Least significant bit first
+///
+/// The first color in byte occupies the lowest bits:
+///
+/// `0b33221100 ...` (for 2-bit colors)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LSB;
+
+impl BitOrder for LSB {
+    fn bit_offset(index: usize, bits: usize) -> usize {
+        index * bits
+    }
+}
+
+/// Most significant bit first
+///
+/// The first color in byte occupies the highest bits:
+///
+/// `0b00112233 ...` (for 2-bit colors)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MSB;
+
+impl BitOrder for MSB {
+    fn bit_offset(index: usize, bits: usize) -> usize {
+        8 - bits - index * bits
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn lsb_bits() {
+        let mut buf = [0b10_01_00_11, 0];
+
+        assert_eq!(LSB::get_bits(&buf, 0, 2), 0b11);
+        assert_eq!(LSB::get_bits(&buf, 1, 2), 0b00);
+        assert_eq!(LSB::get_bits(&buf, 2, 2), 0b01);
+        assert_eq!(LSB::get_bits(&buf, 3, 2), 0b10);
+
+        LSB::set_bits(&mut buf, 1, 2, 0b10);
+        LSB::set_bits(&mut buf, 4, 2, 0b01);
+
+        assert_eq!(&buf, &[0b10_01_10_11, 0b00_00_00_01]);
+    }
+
+    #[test]
+    fn msb_bits() {
+        let mut buf = [0b10_01_00_11, 0];
+
+        assert_eq!(MSB::get_bits(&buf, 0, 2), 0b10);
+        assert_eq!(MSB::get_bits(&buf, 1, 2), 0b01);
+        assert_eq!(MSB::get_bits(&buf, 2, 2), 0b00);
+        assert_eq!(MSB::get_bits(&buf, 3, 2), 0b11);
+
+        MSB::set_bits(&mut buf, 2, 2, 0b10);
+        MSB::set_bits(&mut buf, 4, 2, 0b01);
+
+        assert_eq!(&buf, &[0b10_01_10_11, 0b01_00_00_00]);
+    }
+
+    #[test]
+    fn msb_bit() {
+        let mut buf = [0b1000_0001];
+
+        assert_eq!(MSB::get_bits(&buf, 0, 1), 1);
+        assert_eq!(MSB::get_bits(&buf, 1, 1), 0);
+        assert_eq!(MSB::get_bits(&buf, 7, 1), 1);
+
+        MSB::set_bits(&mut buf, 0, 1, 0);
+        MSB::set_bits(&mut buf, 1, 1, 1);
+
+        assert_eq!(&buf, &[0b0100_0001]);
+    }
+}
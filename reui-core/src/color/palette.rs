@@ -0,0 +1,257 @@
+use crate::ConstDefault;
+use super::{ColorFmt, ColorBuf, BW, GS, GSA, RGB, RGBA, HSV, HSL, YCbCr};
+
+/// Perceptual distance between colors
+pub trait ColorDistance {
+    /// Get distance to other color
+    ///
+    /// The distance is zero for equal colors and grows with visible difference.
+    fn distance(&self, other: &Self) -> u32;
+}
+
+/// Weighted distance between RGB components
+///
+/// Uses "redmean" approximation which weights red and blue depending on red level.
+fn rgb_distance(a: &RGB, b: &RGB) -> u32 {
+    let mean = (a.r as u32 + b.r as u32) / 2;
+    let (dr, dg, db) = (a.r as i32 - b.r as i32, a.g as i32 - b.g as i32, a.b as i32 - b.b as i32);
+
+    (((512 + mean) * (dr * dr) as u32) >> 8) + 4 * (dg * dg) as u32 + (((767 - mean) * (db * db) as u32) >> 8)
+}
+
+impl ColorDistance for RGB {
+    fn distance(&self, other: &Self) -> u32 {
+        rgb_distance(self, other)
+    }
+}
+
+impl ColorDistance for RGBA {
+    fn distance(&self, other: &Self) -> u32 {
+        let da = self.a as i32 - other.a as i32;
+        rgb_distance(&(*self).into(), &(*other).into()) + 3 * (da * da) as u32
+    }
+}
+
+impl ColorDistance for GS {
+    fn distance(&self, other: &Self) -> u32 {
+        let dv = self.v as i32 - other.v as i32;
+        (dv * dv) as u32
+    }
+}
+
+impl ColorDistance for GSA {
+    fn distance(&self, other: &Self) -> u32 {
+        let (dv, da) = (self.v as i32 - other.v as i32, self.a as i32 - other.a as i32);
+        (dv * dv + da * da) as u32
+    }
+}
+
+impl ColorDistance for BW {
+    fn distance(&self, other: &Self) -> u32 {
+        (self != other) as u32
+    }
+}
+
+impl ColorDistance for u8 {
+    fn distance(&self, other: &Self) -> u32 {
+        let dv = *self as i32 - *other as i32;
+        (dv * dv) as u32
+    }
+}
+
+impl ColorDistance for YCbCr {
+    fn distance(&self, other: &Self) -> u32 {
+        let dy = self.y as i32 - other.y as i32;
+        let (dcb, dcr) = (self.cb as i32 - other.cb as i32, self.cr as i32 - other.cr as i32);
+        (dy * dy + dcb * dcb + dcr * dcr) as u32
+    }
+}
+
+/// Distance between hues around color circle scaled to 0..=255
+fn hue_distance(a: u16, b: u16) -> i32 {
+    let d = (a as i32 - b as i32).abs() % 360;
+    d.min(360 - d) * 255 / 180
+}
+
+impl ColorDistance for HSV {
+    fn distance(&self, other: &Self) -> u32 {
+        let dh = hue_distance(self.h, other.h);
+        let (ds, dv) = (self.s as i32 - other.s as i32, self.v as i32 - other.v as i32);
+        (dh * dh + ds * ds + dv * dv) as u32
+    }
+}
+
+impl ColorDistance for HSL {
+    fn distance(&self, other: &Self) -> u32 {
+        let dh = hue_distance(self.h, other.h);
+        let (ds, dl) = (self.s as i32 - other.s as i32, self.l as i32 - other.l as i32);
+        (dh * dh + ds * ds + dl * dl) as u32
+    }
+}
+
+/// Find index of nearest color in first colors of palette
+pub fn nearest_color<Buf>(palette: &Buf, limit: usize, color: &<Buf::ColorFmt as ColorFmt>::ColorType) -> usize
+where
+    Buf: ColorBuf,
+    <Buf::ColorFmt as ColorFmt>::ColorType: ColorDistance,
+{
+    let mut nearest = (0, u32::MAX);
+
+    for index in 0..palette.len().min(limit) {
+        let distance = palette.get(index).distance(color);
+
+        if distance < nearest.1 {
+            nearest = (index, distance);
+
+            if distance == 0 {
+                break;
+            }
+        }
+    }
+
+    nearest.0
+}
+
+/// Palette lookup method
+///
+/// Lookup finds index of palette color for arbitrary color.
+pub trait PaletteLookup<Color> {
+    /// Get index of palette color which is nearest to color
+    fn lookup<Buf>(&self, palette: &Buf, limit: usize, color: &Color) -> usize
+    where
+        Buf: ColorBuf,
+        Buf::ColorFmt: ColorFmt<ColorType = Color>;
+}
+
+/// Lookup by scanning whole palette
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NearestColor;
+
+impl ConstDefault for NearestColor {
+    const DEFAULT: Self = NearestColor;
+}
+
+impl<Color: ColorDistance> PaletteLookup<Color> for NearestColor {
+    fn lookup<Buf>(&self, palette: &Buf, limit: usize, color: &Color) -> usize
+    where
+        Buf: ColorBuf,
+        Buf::ColorFmt: ColorFmt<ColorType = Color>,
+    {
+        nearest_color(palette, limit, color)
+    }
+}
+
+/// Precomputed reverse lookup table
+///
+/// Maps colors quantized to 4 bits per channel to indexes of nearest palette colors,
+/// which allows lookup in constant time at cost of 4KiB of memory.
+#[derive(Clone)]
+pub struct ReverseLut {
+    table: [u8; 4096],
+}
+
+impl ReverseLut {
+    /// Build lookup table for first colors of palette
+    pub fn new<Buf>(palette: &Buf, limit: usize) -> Self
+    where
+        Buf: ColorBuf,
+        <Buf::ColorFmt as ColorFmt>::ColorType: ColorDistance + From<RGB>,
+    {
+        let mut table = [0u8; 4096];
+
+        for (index, entry) in table.iter_mut().enumerate() {
+            // center of cell
+            let channel = |shift: usize| (((index >> shift) & 0xf) as u8) << 4 | 0x8;
+            let color = RGB::new(channel(8), channel(4), channel(0)).into();
+
+            *entry = nearest_color(palette, limit.min(256), &color) as u8;
+        }
+
+        Self { table }
+    }
+
+    /// Get index of palette color for RGB color
+    pub fn get(&self, color: RGB) -> usize {
+        self.table[(color.r as usize >> 4) << 8 | (color.g as usize >> 4) << 4 | color.b as usize >> 4] as usize
+    }
+}
+
+impl<Color> PaletteLookup<Color> for ReverseLut
+where
+    Color: Clone + Into<RGB>,
+{
+    fn lookup<Buf>(&self, _palette: &Buf, _limit: usize, color: &Color) -> usize
+    where
+        Buf: ColorBuf,
+        Buf::ColorFmt: ColorFmt<ColorType = Color>,
+    {
+        self.get(color.clone().into())
+    }
+}
+
+impl<Color, Lookup> PaletteLookup<Color> for &Lookup
+where
+    Lookup: PaletteLookup<Color>,
+{
+    fn lookup<Buf>(&self, palette: &Buf, limit: usize, color: &Color) -> usize
+    where
+        Buf: ColorBuf,
+        Buf::ColorFmt: ColorFmt<ColorType = Color>,
+    {
+        (**self).lookup(palette, limit, color)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::format;
+
+    const PALETTE: [u8; 12] = [0, 0, 0, 255, 255, 255, 255, 0, 0, 0, 0, 128];
+
+    #[test]
+    fn distance() {
+        assert_eq!(RGB::new(1, 2, 3).distance(&RGB::new(1, 2, 3)), 0);
+        assert!(RGB::new(0, 0, 0).distance(&RGB::new(0, 50, 0)) > RGB::new(0, 0, 0).distance(&RGB::new(0, 0, 50)));
+        assert_eq!(GS::new(10).distance(&GS::new(13)), 9);
+        assert_eq!(GSA::new(10, 0).distance(&GSA::new(13, 4)), 25);
+        assert_eq!(BW::from(true).distance(&BW::from(false)), 1);
+        assert!(RGBA::new(0, 0, 0, 0).distance(&RGBA::new(0, 0, 0, 255)) > 0);
+        assert_eq!(7u8.distance(&3), 16);
+        assert_eq!(YCbCr::new(10, 128, 130).distance(&YCbCr::new(10, 125, 128)), 13);
+        assert_eq!(HSV::new(350, 0, 0).distance(&HSV::new(10, 0, 0)), HSV::new(0, 0, 0).distance(&HSV::new(20, 0, 0)));
+        assert_eq!(HSL::new(0, 0, 0).distance(&HSL::new(180, 0, 0)), 255 * 255);
+    }
+
+    #[test]
+    fn nearest() {
+        let palette = (format::RGB888, PALETTE);
+
+        assert_eq!(nearest_color(&palette, 4, &RGB::new(255, 0, 0)), 2);
+        assert_eq!(nearest_color(&palette, 4, &RGB::new(200, 30, 10)), 2);
+        assert_eq!(nearest_color(&palette, 4, &RGB::new(10, 10, 90)), 3);
+        assert_eq!(nearest_color(&palette, 2, &RGB::new(10, 10, 90)), 0);
+        assert_eq!(nearest_color(&palette, 4, &RGB::new(200, 200, 200)), 1);
+        assert_eq!(NearestColor.lookup(&palette, 16, &RGB::new(250, 250, 250)), 1);
+    }
+
+    #[test]
+    fn reverse() {
+        let palette = (format::RGB888, PALETTE);
+        let lut = ReverseLut::new(&palette, 4);
+
+        assert_eq!(lut.get(RGB::new(255, 0, 0)), 2);
+        assert_eq!(lut.get(RGB::new(10, 10, 90)), 3);
+        assert_eq!(lut.lookup(&palette, 4, &RGB::new(200, 200, 200)), 1);
+
+        for r in (0..=255).step_by(17) {
+            for b in (0..=255).step_by(17) {
+                let color = RGB::new(r, 0, b);
+                let index = lut.get(color);
+
+                assert!(index < 4);
+                assert_eq!(lut.get(RGB::new(r | 0xf, 0xf, b | 0xf)), index);
+            }
+        }
+    }
+}
@@ -1,15 +1,28 @@
+use core::ops::Range;
+use core::marker::PhantomData;
 use crate::{ConstDefault};
-use super::{ColorFmt, ColorGet, ColorSet};
+use super::{ColorFmt, ColorGet, ColorSet, ColorAlpha, BitOrder, LSB, ColorSpans, set_bits, set_bytes};
+
+impl ColorAlpha for u8 {
+    fn alpha(&self) -> u8 {
+        255
+    }
+}
 
 /// 1-bit raw value
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub struct RAW1;
+pub struct RAW1<Order = LSB> {
+    _order: PhantomData<Order>,
+}
 
-impl ConstDefault for RAW1 {
-    const DEFAULT: Self = Self;
+/// RAW1 format with LSB-first bit order
+pub const RAW1: RAW1 = RAW1 { _order: PhantomData };
+
+impl<Order> ConstDefault for RAW1<Order> {
+    const DEFAULT: Self = Self { _order: PhantomData };
 }
 
-impl ColorFmt for RAW1 {
+impl<Order> ColorFmt for RAW1<Order> {
     type ColorType = u8;
     type ColorBits = typenum::U1;
 
@@ -18,35 +31,49 @@ impl ColorFmt for RAW1 {
     }
 }
 
-impl ColorGet for RAW1 {
+impl<Order> RAW1<Order> {
+    fn unpack(bits: u8) -> u8 {
+        if bits > 0 { 255 } else { 0 }
+    }
+
+    fn pack(color: u8) -> u8 {
+        (color > 0) as u8
+    }
+}
+
+impl<Order: BitOrder> ColorGet for RAW1<Order> {
     fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
-        let byte = buffer[index / 8];
-        let bit = 1u8 << (index % 8);
-        if (byte & bit) > 0 { 255 } else { 0 }
+        Self::unpack(Order::get_bits(buffer, index, 1))
     }
 }
 
-impl ColorSet for RAW1 {
+impl<Order: BitOrder> ColorSet for RAW1<Order> {
     fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
-        let byte = &mut buffer[index / 8];
-        let bit = 1u8 << (index % 8);
-        if color > 0 {
-            *byte |= bit;
-        } else {
-            *byte &= !bit;
-        }
+        Order::set_bits(buffer, index, 1, Self::pack(color));
+    }
+
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        set_bits::<typenum::U1, Order, _>(buffer, ColorSpans::new(range, length, stride), colors, Self::pack);
     }
 }
 
 /// 2-bit raw value
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub struct RAW2;
+pub struct RAW2<Order = LSB> {
+    _order: PhantomData<Order>,
+}
 
-impl ConstDefault for RAW2 {
-    const DEFAULT: Self = Self;
+/// RAW2 format with LSB-first bit order
+pub const RAW2: RAW2 = RAW2 { _order: PhantomData };
+
+impl<Order> ConstDefault for RAW2<Order> {
+    const DEFAULT: Self = Self { _order: PhantomData };
 }
 
-impl ColorFmt for RAW2 {
+impl<Order> ColorFmt for RAW2<Order> {
     type ColorType = u8;
     type ColorBits = typenum::U2;
 
@@ -55,32 +82,49 @@ impl ColorFmt for RAW2 {
     }
 }
 
-impl ColorGet for RAW2 {
+impl<Order> RAW2<Order> {
+    fn unpack(bits: u8) -> u8 {
+        bits
+    }
+
+    fn pack(color: u8) -> u8 {
+        color
+    }
+}
+
+impl<Order: BitOrder> ColorGet for RAW2<Order> {
     fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
-        let byte = buffer[index / 4];
-        let off = (index % 4) * 2;
-        (byte >> off) & 0b11
+        Self::unpack(Order::get_bits(buffer, index, 2))
     }
 }
 
-impl ColorSet for RAW2 {
+impl<Order: BitOrder> ColorSet for RAW2<Order> {
     fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
-        let byte = &mut buffer[index / 4];
-        let off = (index % 4) * 2;
-        *byte &= !(0b11 << off);
-        *byte |= (color & 0b11) << off;
+        Order::set_bits(buffer, index, 2, Self::pack(color));
+    }
+
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        set_bits::<typenum::U2, Order, _>(buffer, ColorSpans::new(range, length, stride), colors, Self::pack);
     }
 }
 
 /// 4-bit raw value
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub struct RAW4;
+pub struct RAW4<Order = LSB> {
+    _order: PhantomData<Order>,
+}
 
-impl ConstDefault for RAW4 {
-    const DEFAULT: Self = Self;
+/// RAW4 format with LSB-first bit order
+pub const RAW4: RAW4 = RAW4 { _order: PhantomData };
+
+impl<Order> ConstDefault for RAW4<Order> {
+    const DEFAULT: Self = Self { _order: PhantomData };
 }
 
-impl ColorFmt for RAW4 {
+impl<Order> ColorFmt for RAW4<Order> {
     type ColorType = u8;
     type ColorBits = typenum::U4;
 
@@ -89,20 +133,32 @@ impl ColorFmt for RAW4 {
     }
 }
 
-impl ColorGet for RAW4 {
+impl<Order> RAW4<Order> {
+    fn unpack(bits: u8) -> u8 {
+        bits
+    }
+
+    fn pack(color: u8) -> u8 {
+        color
+    }
+}
+
+impl<Order: BitOrder> ColorGet for RAW4<Order> {
     fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
-        let byte = buffer[index / 2];
-        let off = (index % 2) * 4;
-        (byte >> off) & 0b1111
+        Self::unpack(Order::get_bits(buffer, index, 4))
     }
 }
 
-impl ColorSet for RAW4 {
+impl<Order: BitOrder> ColorSet for RAW4<Order> {
     fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
-        let byte = &mut buffer[index / 2];
-        let off = (index % 2) * 4;
-        *byte &= !(0b1111 << off);
-        *byte |= (color & 0b1111) << off;
+        Order::set_bits(buffer, index, 4, Self::pack(color));
+    }
+
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        set_bits::<typenum::U4, Order, _>(buffer, ColorSpans::new(range, length, stride), colors, Self::pack);
     }
 }
 
@@ -123,14 +179,31 @@ impl ColorFmt for RAW8 {
     }
 }
 
+impl RAW8 {
+    fn unpack(bytes: &[u8]) -> u8 {
+        bytes[0]
+    }
+
+    fn pack(color: u8, bytes: &mut [u8]) {
+        bytes[0] = color;
+    }
+}
+
 impl ColorGet for RAW8 {
     fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
-        buffer[index]
+        Self::unpack(&buffer[index..index + 1])
     }
 }
 
 impl ColorSet for RAW8 {
     fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
-        buffer[index] = color;
+        Self::pack(color, &mut buffer[index..index + 1]);
+    }
+
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        set_bytes::<typenum::U1, _>(buffer, ColorSpans::new(range, length, stride), colors, Self::pack);
     }
 }
@@ -1,5 +1,6 @@
+use core::ops::Range;
 use crate::{ConstDefault};
-use super::{ColorFmt, ColorGet, ColorSet, GS};
+use super::{ColorFmt, ColorGet, ColorSet, ColorAlpha, BW, GS, GSA, ColorSpans, set_bytes, lerp};
 
 /// RGB
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -14,6 +15,28 @@ impl RGB {
     pub fn new(r: u8, g: u8, b: u8) -> Self {
         Self { r, g, b }
     }
+
+    /// Get luminance of color
+    ///
+    /// Uses integer approximation of BT.601 weights: `(77 * r + 150 * g + 29 * b) / 256`.
+    pub fn luma(&self) -> u8 {
+        ((77 * self.r as u16 + 150 * self.g as u16 + 29 * self.b as u16) >> 8) as u8
+    }
+
+    /// Mix with other color using fraction of 255
+    pub fn mix(self, other: Self, t: u8) -> Self {
+        Self::new(lerp(self.r, other.r, t), lerp(self.g, other.g, t), lerp(self.b, other.b, t))
+    }
+
+    /// Mix with white using fraction of 255
+    pub fn lighten(self, amount: u8) -> Self {
+        self.mix(Self::new(255, 255, 255), amount)
+    }
+
+    /// Mix with black using fraction of 255
+    pub fn darken(self, amount: u8) -> Self {
+        self.mix(Self::new(0, 0, 0), amount)
+    }
 }
 
 impl From<(u8, u8, u8)> for RGB {
@@ -22,9 +45,8 @@ impl From<(u8, u8, u8)> for RGB {
     }
 }
 
-impl Into<(u8, u8, u8)> for RGB {
-    fn into(self) -> (u8, u8, u8) {
-        let RGB { r, g, b} = self;
+impl From<RGB> for (u8, u8, u8) {
+    fn from(RGB { r, g, b }: RGB) -> Self {
         (r, g, b)
     }
 }
@@ -35,10 +57,21 @@ impl From<u8> for RGB {
     }
 }
 
-impl Into<u8> for RGB {
-    fn into(self) -> u8 {
-        let RGB { r, g, b} = self;
-        ((r as u16 + g as u16 + b as u16) / 3) as u8
+impl From<RGB> for u8 {
+    fn from(rgb: RGB) -> Self {
+        rgb.luma()
+    }
+}
+
+impl From<BW> for RGB {
+    fn from(bw: BW) -> Self {
+        GS::from(bw).into()
+    }
+}
+
+impl From<RGB> for BW {
+    fn from(rgb: RGB) -> Self {
+        GS::from(rgb).into()
     }
 }
 
@@ -49,9 +82,27 @@ impl From<GS> for RGB {
     }
 }
 
-impl Into<GS> for RGB {
-    fn into(self) -> GS {
-        GS::new(self.into())
+impl From<RGB> for GS {
+    fn from(rgb: RGB) -> Self {
+        GS::new(rgb.luma())
+    }
+}
+
+impl From<GSA> for RGB {
+    fn from(gsa: GSA) -> Self {
+        GS::from(gsa).into()
+    }
+}
+
+impl From<RGB> for GSA {
+    fn from(rgb: RGB) -> Self {
+        GS::from(rgb).into()
+    }
+}
+
+impl ColorAlpha for RGB {
+    fn alpha(&self) -> u8 {
+        255
     }
 }
 
@@ -76,21 +127,38 @@ impl ColorFmt for RGB332 {
     }
 }
 
-impl ColorGet for RGB332 {
-    fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
-        let byte = buffer[index];
+impl RGB332 {
+    fn unpack(bytes: &[u8]) -> RGB {
+        let byte = bytes[0];
 
-        Self::ColorType {
+        RGB {
             r: (byte >> 5) * (255 / 7),
             g: ((byte >> 2) & 0b111) * (255 / 7),
             b: (byte & 0b11) * (255 / 3),
         }
     }
+
+    fn pack(RGB { r, g, b }: RGB, bytes: &mut [u8]) {
+        bytes[0] = ((r >> (8 - 3)) << 5) | ((g >> (8 - 3)) << 2) | (b >> (8 - 2));
+    }
+}
+
+impl ColorGet for RGB332 {
+    fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
+        Self::unpack(&buffer[index..index + 1])
+    }
 }
 
 impl ColorSet for RGB332 {
-    fn set_color(&self, buffer: &mut [u8], index: usize, RGB { r, g, b }: Self::ColorType) {
-        buffer[index] = ((r >> (8 - 3)) << 5) | ((g >> (8 - 3)) << 2) | (b >> (8 - 2));
+    fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
+        Self::pack(color, &mut buffer[index..index + 1]);
+    }
+
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        set_bytes::<typenum::U1, _>(buffer, ColorSpans::new(range, length, stride), colors, Self::pack);
     }
 }
 
@@ -142,7 +210,7 @@ impl ColorGet for RGB444 {
 impl ColorSet for RGB444 {
     fn set_color(&self, buffer: &mut [u8], index: usize, RGB { r, g, b }: Self::ColorType) {
         let i = index * 3 / 2;
-        let bs = &mut buffer[i..i + 1];
+        let bs = &mut buffer[i..i + 2];
 
         if (index % 2) > 0 {
             // (MSB) ----rrrr ggggbbbb
@@ -179,28 +247,44 @@ impl ColorFmt for RGB565 {
     }
 }
 
-impl ColorGet for RGB565 {
-    fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
-        let i = index * 2;
-        let b0 = buffer[i + 0];
-        let b1 = buffer[i + 1];
+impl RGB565 {
+    fn unpack(bytes: &[u8]) -> RGB {
+        let b0 = bytes[0];
+        let b1 = bytes[1];
 
         // (MSB) rrrrrggg gggbbbbb
-        Self::ColorType {
+        RGB {
             r: (b0 >> 3) * (255 / 31),
             g: (((b0 & 0b111) << 3) | (b1 >> 5)) * (255 / 63),
             b: (b1 & 0b11111) * (255 / 31),
         }
     }
+
+    fn pack(RGB { r, g, b }: RGB, bytes: &mut [u8]) {
+        // (MSB) rrrrrggg gggbbbbb
+        bytes[0] = (r & (0b11111 << 3)) | (g >> 5);
+        bytes[1] = ((g & (0b111 << 2)) << 3) | (b >> 3);
+    }
+}
+
+impl ColorGet for RGB565 {
+    fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
+        let i = index * 2;
+        Self::unpack(&buffer[i..i + 2])
+    }
 }
 
 impl ColorSet for RGB565 {
-    fn set_color(&self, buffer: &mut [u8], index: usize, RGB { r, g, b }: Self::ColorType) {
+    fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
         let i = index * 2;
+        Self::pack(color, &mut buffer[i..i + 2]);
+    }
 
-        // (MSB) rrrrrggg gggbbbbb
-        buffer[i] = (r & (0b11111 << 3)) | (g >> 5);
-        buffer[i + 1] = ((g & (0b111 << 2)) << 3) | (b >> 3);
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        set_bytes::<typenum::U2, _>(buffer, ColorSpans::new(range, length, stride), colors, Self::pack);
     }
 }
 
@@ -221,24 +305,87 @@ impl ColorFmt for RGB888 {
     }
 }
 
+impl RGB888 {
+    fn unpack(bytes: &[u8]) -> RGB {
+        RGB {
+            r: bytes[0],
+            g: bytes[1],
+            b: bytes[2],
+        }
+    }
+
+    fn pack(RGB { r, g, b }: RGB, bytes: &mut [u8]) {
+        bytes[0] = r;
+        bytes[1] = g;
+        bytes[2] = b;
+    }
+}
+
 impl ColorGet for RGB888 {
     fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
         let i = index * 3;
-
-        let r = buffer[i + 0];
-        let g = buffer[i + 1];
-        let b = buffer[i + 2];
-
-        Self::ColorType { r, g, b }
+        Self::unpack(&buffer[i..i + 3])
     }
 }
 
 impl ColorSet for RGB888 {
-    fn set_color(&self, buffer: &mut [u8], index: usize, RGB { r, g, b }: Self::ColorType) {
+    fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
         let i = index * 3;
+        Self::pack(color, &mut buffer[i..i + 3]);
+    }
+
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        set_bytes::<typenum::U3, _>(buffer, ColorSpans::new(range, length, stride), colors, Self::pack);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn mix() {
+        assert_eq!(RGB::new(0, 100, 200).mix(RGB::new(255, 100, 0), 51), RGB::new(51, 100, 160));
+        assert_eq!(RGB::new(0, 100, 200).lighten(255), RGB::new(255, 255, 255));
+        assert_eq!(RGB::new(0, 100, 200).darken(128), RGB::new(0, 50, 100));
+    }
+
+    #[test]
+    fn rgb444_colors() {
+        let fmt = RGB444;
+        let mut buf = [0u8; 6];
+
+        fmt.set_colors(&mut buf, 0..4, 1, 2, [RGB::new(0x10, 0x20, 0x30), RGB::new(0xff, 0xff, 0xff)].iter().cloned());
+
+        assert_eq!(&buf, &[0x12, 0x30, 0, 0xff, 0xf0, 0]);
+
+        fmt.set_color(&mut buf, 1, RGB::new(0xa0, 0xb0, 0xc0));
+
+        assert_eq!(&buf, &[0x12, 0x3a, 0xbc, 0xff, 0xf0, 0]);
+        assert!(fmt.get_colors(&buf, 0..4, 2, 2).eq([
+            RGB::new(17, 34, 51),
+            RGB::new(170, 187, 204),
+            RGB::new(255, 255, 255),
+            RGB::new(0, 0, 0),
+        ].iter().cloned()));
+    }
+
+    #[test]
+    fn rgb565_colors() {
+        let fmt = RGB565;
+        let mut buf = [0u8; 8];
+
+        fmt.set_colors(&mut buf, 0..4, 1, 2, [RGB::new(255, 0, 0), RGB::new(0, 0, 255)].iter().cloned());
 
-        buffer[i + 0] = r;
-        buffer[i + 1] = g;
-        buffer[i + 2] = b;
+        assert_eq!(&buf, &[0xf8, 0, 0, 0, 0, 0x1f, 0, 0]);
+        assert!(fmt.get_colors(&buf, 0..4, 4, 4).eq([
+            RGB::new(248, 0, 0),
+            RGB::new(0, 0, 0),
+            RGB::new(0, 0, 248),
+            RGB::new(0, 0, 0),
+        ].iter().cloned()));
     }
 }
@@ -1,5 +1,6 @@
+use core::ops::Range;
 use crate::{ConstDefault};
-use super::{ColorFmt, ColorGet, ColorSet, GS, GSA, RGB};
+use super::{ColorFmt, ColorGet, ColorSet, ColorAlpha, BW, GS, GSA, RGB, ColorSpans, set_bytes, lerp};
 
 /// RGB with alpha
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -15,6 +16,11 @@ impl RGBA {
     pub fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
         RGBA { r, g, b, a }
     }
+
+    /// Mix with other color including alpha using fraction of 255
+    pub fn mix(self, other: Self, t: u8) -> Self {
+        Self::new(lerp(self.r, other.r, t), lerp(self.g, other.g, t), lerp(self.b, other.b, t), lerp(self.a, other.a, t))
+    }
 }
 
 impl Default for RGBA {
@@ -29,9 +35,9 @@ impl From<(u8, u8, u8, u8)> for RGBA {
     }
 }
 
-impl Into<(u8, u8, u8, u8)> for RGBA {
-    fn into(self) -> (u8, u8, u8, u8) {
-        (self.r, self.g, self.b, self.a)
+impl From<RGBA> for (u8, u8, u8, u8) {
+    fn from(RGBA { r, g, b, a }: RGBA) -> Self {
+        (r, g, b, a)
     }
 }
 
@@ -41,9 +47,9 @@ impl From<(u8, u8, u8)> for RGBA {
     }
 }
 
-impl Into<(u8, u8, u8)> for RGBA {
-    fn into(self) -> (u8, u8, u8) {
-        (self.r, self.g, self.b)
+impl From<RGBA> for (u8, u8, u8) {
+    fn from(RGBA { r, g, b, .. }: RGBA) -> Self {
+        (r, g, b)
     }
 }
 
@@ -53,10 +59,21 @@ impl From<u8> for RGBA {
     }
 }
 
-impl Into<u8> for RGBA {
-    fn into(self) -> u8 {
-        let RGBA { r, g, b, .. } = self;
-        ((r as u16 + g as u16 + b as u16) / 3) as u8
+impl From<RGBA> for u8 {
+    fn from(rgba: RGBA) -> Self {
+        RGB::from(rgba).luma()
+    }
+}
+
+impl From<BW> for RGBA {
+    fn from(bw: BW) -> Self {
+        GS::from(bw).into()
+    }
+}
+
+impl From<RGBA> for BW {
+    fn from(rgba: RGBA) -> Self {
+        GS::from(rgba).into()
     }
 }
 
@@ -67,9 +84,9 @@ impl From<GS> for RGBA {
     }
 }
 
-impl Into<GS> for RGBA {
-    fn into(self) -> GS {
-        GS::new(self.into())
+impl From<RGBA> for GS {
+    fn from(rgba: RGBA) -> Self {
+        GS::new(rgba.into())
     }
 }
 
@@ -79,9 +96,9 @@ impl From<GSA> for RGBA {
     }
 }
 
-impl Into<GSA> for RGBA {
-    fn into(self) -> GSA {
-        GSA::new(self.into(), self.a)
+impl From<RGBA> for GSA {
+    fn from(rgba: RGBA) -> Self {
+        GSA::new(rgba.into(), rgba.a)
     }
 }
 
@@ -91,13 +108,18 @@ impl From<RGB> for RGBA {
     }
 }
 
-impl Into<RGB> for RGBA {
-    fn into(self) -> RGB {
-        let RGBA { r, g, b, .. } = self;
+impl From<RGBA> for RGB {
+    fn from(RGBA { r, g, b, .. }: RGBA) -> Self {
         RGB::new(r, g, b)
     }
 }
 
+impl ColorAlpha for RGBA {
+    fn alpha(&self) -> u8 {
+        self.a
+    }
+}
+
 /// RGBA with 4-bits per component
 ///
 /// Single color per each two bytes:
@@ -112,36 +134,49 @@ impl ConstDefault for RGBA4444 {
 
 impl ColorFmt for RGBA4444 {
     type ColorType = RGBA;
-    type ColorBits = typenum::U8;
+    type ColorBits = typenum::U16;
 
     fn num_colors(&self, buffer: &[u8]) -> usize {
         buffer.len() / 2
     }
 }
 
+impl RGBA4444 {
+    fn unpack(bytes: &[u8]) -> RGBA {
+        // (MSB) rrrrgggg bbbbaaaa
+        RGBA {
+            r: (bytes[0] >> 4) * (255/15),
+            g: (bytes[0] & 0b1111) * (255/15),
+            b: (bytes[1] >> 4) * (255/15),
+            a: (bytes[1] & 0b1111) * (255/15),
+        }
+    }
+
+    fn pack(RGBA { r, g, b, a }: RGBA, bytes: &mut [u8]) {
+        // (MSB) rrrrgggg bbbbaaaa
+        bytes[0] = (r & (0b1111 << 4)) | (g >> 4);
+        bytes[1] = (b & (0b1111 << 4)) | (a >> 4);
+    }
+}
+
 impl ColorGet for RGBA4444 {
     fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
         let i = index * 2;
-        let bs = &buffer[i .. i + 2];
-
-        // (MSB) rrrrgggg bbbbaaaa
-        Self::ColorType {
-            r: (bs[0] >> 4) * (255/15),
-            g: (bs[0] & 0b1111) * (255/15),
-            b: (bs[1] >> 4) * (255/15),
-            a: (bs[1] & 0b1111) * (255/15),
-        }
+        Self::unpack(&buffer[i..i + 2])
     }
 }
 
 impl ColorSet for RGBA4444 {
-    fn set_color(&self, buffer: &mut [u8], index: usize, RGBA { r, g, b, a }: Self::ColorType) {
+    fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
         let i = index * 2;
-        let bs = &mut buffer[i .. i + 2];
+        Self::pack(color, &mut buffer[i..i + 2]);
+    }
 
-        // (MSB) rrrrgggg bbbbaaaa
-        bs[0] = (r & (0b1111 << 4)) | (g >> 4);
-        bs[1] = (b & (0b1111 << 4)) | (a >> 4);
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        set_bytes::<typenum::U2, _>(buffer, ColorSpans::new(range, length, stride), colors, Self::pack);
     }
 }
 
@@ -159,36 +194,49 @@ impl ConstDefault for RGBA5551 {
 
 impl ColorFmt for RGBA5551 {
     type ColorType = RGBA;
-    type ColorBits = typenum::U8;
+    type ColorBits = typenum::U16;
 
     fn num_colors(&self, buffer: &[u8]) -> usize {
         buffer.len() / 2
     }
 }
 
+impl RGBA5551 {
+    fn unpack(bytes: &[u8]) -> RGBA {
+        // (MSB) rrrrrggg ggbbbbba
+        RGBA {
+            r: (bytes[0] >> 3) * (255/31),
+            g: (((bytes[0] & 7) << 2) | (bytes[1] >> 6)) * (255/31),
+            b: ((bytes[1] >> 1) & 31) * (255/31),
+            a: if (bytes[1] & 1) > 0 { 255 } else { 0 },
+        }
+    }
+
+    fn pack(RGBA { r, g, b, a }: RGBA, bytes: &mut [u8]) {
+        // (MSB) rrrrrggg ggbbbbba
+        bytes[0] = (r & (31 << 3)) | (g >> 5);
+        bytes[1] = ((g & (3 << 3)) << 3) | (b >> 3) | (a >> 7);
+    }
+}
+
 impl ColorGet for RGBA5551 {
     fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
         let i = index * 2;
-        let bs = &buffer[i .. i + 2];
-
-        // (MSB) rrrrrggg ggbbbbba
-        Self::ColorType {
-            r: (bs[0] >> 3) * (255/31),
-            g: (((bs[0] & 7) << 2) | (bs[1] >> 6)) * (255/31),
-            b: ((bs[1] >> 1) & 31) * (255/31),
-            a: if (bs[1] & 1) > 0 { 255 } else { 0 },
-        }
+        Self::unpack(&buffer[i..i + 2])
     }
 }
 
 impl ColorSet for RGBA5551 {
-    fn set_color(&self, buffer: &mut [u8], index: usize, RGBA { r, g, b, a }: Self::ColorType) {
+    fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
         let i = index * 2;
-        let bs = &mut buffer[i .. i + 2];
+        Self::pack(color, &mut buffer[i..i + 2]);
+    }
 
-        // (MSB) rrrrrggg ggbbbbba
-        bs[0] = (r & (31 << 3)) | (g >> 5);
-        bs[1] = ((g & (3 << 3)) << 3) | (b >> 3) | (a >> 7);
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        set_bytes::<typenum::U2, _>(buffer, ColorSpans::new(range, length, stride), colors, Self::pack);
     }
 }
 
@@ -206,35 +254,48 @@ impl ConstDefault for RGBA8888 {
 
 impl ColorFmt for RGBA8888 {
     type ColorType = RGBA;
-    type ColorBits = typenum::U8;
+    type ColorBits = typenum::U32;
 
     fn num_colors(&self, buffer: &[u8]) -> usize {
         buffer.len() / 4
     }
 }
 
+impl RGBA8888 {
+    fn unpack(bytes: &[u8]) -> RGBA {
+        RGBA {
+            r: bytes[0],
+            g: bytes[1],
+            b: bytes[2],
+            a: bytes[3],
+        }
+    }
+
+    fn pack(RGBA { r, g, b, a }: RGBA, bytes: &mut [u8]) {
+        bytes[0] = r;
+        bytes[1] = g;
+        bytes[2] = b;
+        bytes[3] = a;
+    }
+}
+
 impl ColorGet for RGBA8888 {
     fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
         let i = index * 4;
-        let bs = &buffer[i .. i + 4];
-
-        Self::ColorType {
-            r: bs[0],
-            g: bs[1],
-            b: bs[2],
-            a: bs[3],
-        }
+        Self::unpack(&buffer[i..i + 4])
     }
 }
 
 impl ColorSet for RGBA8888 {
-    fn set_color(&self, buffer: &mut [u8], index: usize, RGBA { r, g, b, a }: Self::ColorType) {
+    fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
         let i = index * 4;
-        let bs = &mut buffer[i .. i + 4];
+        Self::pack(color, &mut buffer[i..i + 4]);
+    }
 
-        bs[0] = r;
-        bs[1] = g;
-        bs[2] = b;
-        bs[3] = a;
+    fn set_colors<Colors>(&self, buffer: &mut [u8], range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Self::ColorType>,
+    {
+        set_bytes::<typenum::U4, _>(buffer, ColorSpans::new(range, length, stride), colors, Self::pack);
     }
 }
@@ -0,0 +1,190 @@
+use core::ops::Range;
+use alloc::{vec, vec::Vec};
+use crate::ConstDefault;
+use super::{ColorFmt, ColorGet, ColorSet, ColorBuf, ColorBufMut, ColorsIter};
+
+/// Heap-allocated buffer for colors
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ColorVec<Fmt> {
+    format: Fmt,
+    len: usize,
+    data: Vec<u8>,
+}
+
+/// Get number of bytes to store colors
+fn bytes_for<Fmt: ColorFmt>(format: &Fmt, len: usize) -> usize {
+    (len * format.color_bits()).div_ceil(8)
+}
+
+impl<Fmt> ColorVec<Fmt>
+where
+    Fmt: ColorFmt,
+{
+    /// Create buffer for specified number of colors filled by zero bytes
+    pub fn new(len: usize) -> Self
+    where
+        Fmt: ConstDefault,
+    {
+        Self::new_with_format(Fmt::DEFAULT, len)
+    }
+
+    /// Create buffer for specified number of colors filled by zero bytes using specified format
+    pub fn new_with_format(format: Fmt, len: usize) -> Self {
+        let data = vec![0; bytes_for(&format, len)];
+        Self { format, len, data }
+    }
+
+    /// Create buffer for specified number of colors filled by color using specified format
+    pub fn new_filled_with_format(format: Fmt, len: usize, color: Fmt::ColorType) -> Self
+    where
+        Fmt: ColorSet,
+        Fmt::ColorType: Clone,
+    {
+        let mut buffer = Self::new_with_format(format, len);
+        buffer.format.fill_colors(&mut buffer.data, 0..len, color);
+        buffer
+    }
+
+    /// Create buffer from bytes
+    ///
+    /// The number of colors is determined by format.
+    pub fn from_bytes(format: Fmt, data: Vec<u8>) -> Self {
+        let len = format.num_colors(&data);
+        Self { format, len, data }
+    }
+
+    /// Get format of colors
+    pub fn format(&self) -> &Fmt {
+        &self.format
+    }
+
+    /// Unwrap bytes of buffer
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Change number of colors in buffer
+    ///
+    /// The added colors is filled by specified color.
+    pub fn resize(&mut self, len: usize, color: Fmt::ColorType)
+    where
+        Fmt: ColorSet,
+        Fmt::ColorType: Clone,
+    {
+        let old_len = self.len.min(len);
+
+        self.data.resize(bytes_for(&self.format, len), 0);
+        self.len = len;
+        self.format.fill_colors(&mut self.data, old_len..len, color);
+    }
+}
+
+impl<Fmt> AsRef<[u8]> for ColorVec<Fmt> {
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl<Fmt> AsMut<[u8]> for ColorVec<Fmt> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+}
+
+impl<Fmt> AsRef<Fmt> for ColorVec<Fmt> {
+    fn as_ref(&self) -> &Fmt {
+        &self.format
+    }
+}
+
+impl<Fmt> ColorBuf for ColorVec<Fmt>
+where
+    Fmt: ColorGet,
+{
+    type ColorFmt = Fmt;
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn get(&self, index: usize) -> Fmt::ColorType {
+        self.format.get_color(&self.data, index)
+    }
+
+    fn gets(&self, range: Range<usize>, length: usize, stride: usize) -> ColorsIter<'_, Fmt> {
+        self.format.get_colors(&self.data, range, length, stride)
+    }
+
+    fn bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    fn color_bits(&self) -> usize {
+        self.format.color_bits()
+    }
+}
+
+impl<Fmt> ColorBufMut for ColorVec<Fmt>
+where
+    Fmt: ColorGet + ColorSet,
+{
+    fn set(&mut self, index: usize, color: Fmt::ColorType) {
+        self.format.set_color(&mut self.data, index, color)
+    }
+
+    fn sets<Colors>(&mut self, range: Range<usize>, length: usize, stride: usize, colors: Colors)
+    where
+        Colors: IntoIterator<Item = Fmt::ColorType>,
+    {
+        self.format.set_colors(&mut self.data, range, length, stride, colors)
+    }
+
+    fn fill(&mut self, range: Range<usize>, color: Fmt::ColorType)
+    where
+        Fmt::ColorType: Clone,
+    {
+        self.format.fill_colors(&mut self.data, range, color)
+    }
+
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{format, GS, RGB};
+
+    #[test]
+    fn new() {
+        let buffer = ColorVec::<format::GS4>::new(5);
+
+        assert_eq!(buffer.bytes(), [0u8; 3]);
+        assert_eq!(buffer.len(), 5);
+
+        let buffer = ColorVec::new_filled_with_format(format::RGB565, 3, RGB::new(0, 252, 0));
+
+        assert!(buffer.bytes().chunks(2).all(|bytes| bytes == [0b0000_0111, 0b1110_0000]));
+    }
+
+    #[test]
+    fn resize() {
+        let mut buffer = ColorVec::new_filled_with_format(format::GS8, 2, GS::new(1));
+
+        buffer.resize(4, GS::new(2));
+        assert_eq!(buffer.bytes(), [1, 1, 2, 2]);
+
+        let cloned = buffer.clone();
+
+        buffer.resize(1, GS::new(3));
+        assert_eq!(buffer.into_bytes(), [1]);
+        assert_eq!(cloned.bytes(), [1, 1, 2, 2]);
+
+        let mut buffer = <ColorVec<format::BW1>>::new(3);
+
+        buffer.resize(10, true.into());
+        assert_eq!(buffer.bytes(), [0b1111_1000, 0b0000_0011]);
+        assert_eq!(ColorVec::from_bytes(<format::BW1>::DEFAULT, vec![0, 0]).len(), 16);
+    }
+}
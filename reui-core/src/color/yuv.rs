@@ -0,0 +1,337 @@
+use core::ops::Range;
+use crate::ConstDefault;
+use super::{ColorFmt, ColorGet, ColorSet, ColorAlpha, BW, GS, GSA, RGB, RGBA};
+
+/// Luma with blue-difference and red-difference chroma
+///
+/// Uses full range BT.601 encoding as in JPEG, so gray colors has chroma 128.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct YCbCr {
+    pub y: u8,
+    pub cb: u8,
+    pub cr: u8,
+}
+
+impl YCbCr {
+    /// Create YCbCr color from components
+    pub fn new(y: u8, cb: u8, cr: u8) -> Self {
+        Self { y, cb, cr }
+    }
+}
+
+impl Default for YCbCr {
+    fn default() -> Self {
+        Self::new(0, 128, 128)
+    }
+}
+
+fn clamp(v: i32) -> u8 {
+    v.clamp(0, 255) as u8
+}
+
+impl From<RGB> for YCbCr {
+    fn from(RGB { r, g, b }: RGB) -> Self {
+        let (r, g, b) = (r as i32, g as i32, b as i32);
+
+        Self::new(
+            clamp((77 * r + 150 * g + 29 * b + 128) >> 8),
+            clamp(128 + ((-43 * r - 85 * g + 128 * b + 128) >> 8)),
+            clamp(128 + ((128 * r - 107 * g - 21 * b + 128) >> 8)),
+        )
+    }
+}
+
+impl From<YCbCr> for RGB {
+    fn from(YCbCr { y, cb, cr }: YCbCr) -> Self {
+        let (y, cb, cr) = (y as i32, cb as i32 - 128, cr as i32 - 128);
+
+        RGB::new(
+            clamp(y + ((359 * cr + 128) >> 8)),
+            clamp(y - ((88 * cb + 183 * cr + 128) >> 8)),
+            clamp(y + ((454 * cb + 128) >> 8)),
+        )
+    }
+}
+
+impl From<RGBA> for YCbCr {
+    fn from(rgba: RGBA) -> Self {
+        RGB::from(rgba).into()
+    }
+}
+
+impl From<YCbCr> for RGBA {
+    fn from(ycbcr: YCbCr) -> Self {
+        RGB::from(ycbcr).into()
+    }
+}
+
+impl From<GS> for YCbCr {
+    fn from(gs: GS) -> Self {
+        Self::new(gs.v, 128, 128)
+    }
+}
+
+impl From<YCbCr> for GS {
+    fn from(ycbcr: YCbCr) -> Self {
+        GS::new(ycbcr.y)
+    }
+}
+
+impl From<GSA> for YCbCr {
+    fn from(gsa: GSA) -> Self {
+        GS::from(gsa).into()
+    }
+}
+
+impl From<YCbCr> for GSA {
+    fn from(ycbcr: YCbCr) -> Self {
+        GS::from(ycbcr).into()
+    }
+}
+
+impl From<BW> for YCbCr {
+    fn from(bw: BW) -> Self {
+        GS::from(bw).into()
+    }
+}
+
+impl From<YCbCr> for BW {
+    fn from(ycbcr: YCbCr) -> Self {
+        GS::from(ycbcr).into()
+    }
+}
+
+impl ColorAlpha for YCbCr {
+    fn alpha(&self) -> u8 {
+        255
+    }
+}
+
+/// Get color from packed 4:2:2 buffer using offsets of components in macropixel
+fn get_422(buffer: &[u8], index: usize, [y0, cb, y1, cr]: [usize; 4]) -> YCbCr {
+    let base = index / 2 * 4;
+    let y = if index.is_multiple_of(2) { y0 } else { y1 };
+
+    YCbCr::new(buffer[base + y], buffer[base + cb], buffer[base + cr])
+}
+
+/// Set color to packed 4:2:2 buffer using offsets of components in macropixel
+fn set_422(buffer: &mut [u8], index: usize, color: YCbCr, [y0, cb, y1, cr]: [usize; 4]) {
+    let base = index / 2 * 4;
+    let y = if index.is_multiple_of(2) { y0 } else { y1 };
+
+    buffer[base + y] = color.y;
+    buffer[base + cb] = color.cb;
+    buffer[base + cr] = color.cr;
+}
+
+macro_rules! packed_422 {
+    ($(#[$meta:meta])* $type:ident, $offsets:expr) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+        pub struct $type;
+
+        impl ConstDefault for $type {
+            const DEFAULT: Self = Self;
+        }
+
+        impl ColorFmt for $type {
+            type ColorType = YCbCr;
+            type ColorBits = typenum::U16;
+
+            fn num_colors(&self, buffer: &[u8]) -> usize {
+                buffer.len() / 4 * 2
+            }
+        }
+
+        impl ColorGet for $type {
+            fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
+                get_422(buffer, index, $offsets)
+            }
+        }
+
+        impl ColorSet for $type {
+            fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
+                set_422(buffer, index, color, $offsets);
+            }
+
+            fn fill_colors(&self, buffer: &mut [u8], range: Range<usize>, color: Self::ColorType) {
+                for index in range {
+                    self.set_color(buffer, index, color);
+                }
+            }
+        }
+    };
+}
+
+packed_422! {
+    /// Packed YCbCr 4:2:2 with luma first
+    ///
+    /// Each pair of pixels shares chroma, so setting color of pixel changes chroma of its pair:
+    ///
+    /// `Y0 Cb Y1 Cr ...`
+    YUYV, [0, 1, 2, 3]
+}
+
+packed_422! {
+    /// Packed YCbCr 4:2:2 with chroma first
+    ///
+    /// Each pair of pixels shares chroma, so setting color of pixel changes chroma of its pair:
+    ///
+    /// `Cb Y0 Cr Y1 ...`
+    UYVY, [1, 0, 3, 2]
+}
+
+/// Semi-planar YCbCr 4:2:0
+///
+/// Luma plane of `stride * height` bytes is followed by plane of interleaved chroma
+/// with single `Cb Cr` pair for each 2x2 block of pixels. Width and height should be even.
+/// Setting color of pixel changes chroma of its block.
+///
+/// The color index is expected to be `x + y * stride`, so pixel views should use the same stride.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NV12 {
+    pub width: usize,
+    pub height: usize,
+    /// Distance between rows of both planes in bytes
+    pub stride: usize,
+}
+
+impl NV12 {
+    /// Create format for frame of specified size without padding of rows
+    pub const fn new(width: usize, height: usize) -> Self {
+        Self { width, height, stride: width }
+    }
+
+    /// Set distance between rows in bytes
+    pub const fn with_stride(self, stride: usize) -> Self {
+        Self { stride, ..self }
+    }
+
+    /// Get offset of chroma pair for pixel
+    fn chroma(&self, index: usize) -> usize {
+        let (x, y) = (index % self.stride, index / self.stride);
+        self.stride * self.height + y / 2 * self.stride + x / 2 * 2
+    }
+}
+
+impl ColorFmt for NV12 {
+    type ColorType = YCbCr;
+    type ColorBits = typenum::U12;
+
+    fn num_colors(&self, buffer: &[u8]) -> usize {
+        (self.stride * self.height).min(buffer.len() * 2 / 3)
+    }
+}
+
+impl ColorGet for NV12 {
+    fn get_color(&self, buffer: &[u8], index: usize) -> Self::ColorType {
+        let chroma = self.chroma(index);
+        YCbCr::new(buffer[index], buffer[chroma], buffer[chroma + 1])
+    }
+}
+
+impl ColorSet for NV12 {
+    fn set_color(&self, buffer: &mut [u8], index: usize, color: Self::ColorType) {
+        let chroma = self.chroma(index);
+
+        buffer[index] = color.y;
+        buffer[chroma] = color.cb;
+        buffer[chroma + 1] = color.cr;
+    }
+
+    fn fill_colors(&self, buffer: &mut [u8], range: Range<usize>, color: Self::ColorType) {
+        for index in range {
+            self.set_color(buffer, index, color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{format, PixelView, Size, Point, Rect, BlitMode, blit};
+
+    #[test]
+    fn convert() {
+        assert_eq!(YCbCr::from(RGB::new(0, 0, 0)), YCbCr::new(0, 128, 128));
+        assert_eq!(YCbCr::from(RGB::new(255, 255, 255)), YCbCr::new(255, 128, 128));
+        assert_eq!(YCbCr::from(RGB::new(255, 0, 0)), YCbCr::new(77, 85, 255));
+        assert_eq!(YCbCr::from(RGB::new(0, 0, 255)), YCbCr::new(29, 255, 107));
+        assert_eq!(RGB::from(YCbCr::new(77, 85, 255)), RGB::new(255, 1, 1));
+        assert_eq!(RGB::from(YCbCr::new(128, 128, 128)), RGB::new(128, 128, 128));
+        assert_eq!(RGB::from(YCbCr::new(255, 0, 0)), RGB::new(76, 255, 28));
+        assert_eq!(GS::from(YCbCr::new(10, 20, 30)), GS::new(10));
+
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(17) {
+                for b in (0..=255).step_by(51) {
+                    let rgb = RGB::new(r, g, b);
+                    let back = RGB::from(YCbCr::from(rgb));
+
+                    assert!((back.r as i16 - r as i16).abs() <= 2, "{:?} {:?}", rgb, back);
+                    assert!((back.g as i16 - g as i16).abs() <= 2, "{:?} {:?}", rgb, back);
+                    assert!((back.b as i16 - b as i16).abs() <= 2, "{:?} {:?}", rgb, back);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn packed() {
+        let buf = [10, 20, 30, 40, 50, 60, 70, 80];
+
+        assert_eq!(YUYV.num_colors(&buf), 4);
+        assert!(YUYV.get_colors(&buf, 0..4, 4, 4).eq([
+            YCbCr::new(10, 20, 40), YCbCr::new(30, 20, 40),
+            YCbCr::new(50, 60, 80), YCbCr::new(70, 60, 80),
+        ].iter().cloned()));
+        assert_eq!(UYVY.get_color(&buf, 1), YCbCr::new(40, 10, 30));
+
+        let mut buf = [0u8; 8];
+
+        YUYV.set_color(&mut buf, 1, YCbCr::new(1, 2, 3));
+        UYVY.fill_colors(&mut buf, 2..4, YCbCr::new(4, 5, 6));
+
+        assert_eq!(buf, [0, 2, 1, 3, 5, 4, 6, 4]);
+    }
+
+    #[test]
+    fn nv12() {
+        let fmt = NV12::new(4, 2);
+        let mut buf = [0u8; 12];
+
+        assert_eq!(fmt.num_colors(&buf), 8);
+
+        fmt.set_color(&mut buf, 5, YCbCr::new(1, 2, 3));
+        fmt.set_color(&mut buf, 3, YCbCr::new(4, 5, 6));
+
+        assert_eq!(buf, [0, 0, 0, 4, 0, 1, 0, 0, 2, 3, 5, 6]);
+        assert_eq!(fmt.get_color(&buf, 4), YCbCr::new(0, 2, 3));
+        assert_eq!(fmt.get_color(&buf, 7), YCbCr::new(0, 5, 6));
+    }
+
+    #[test]
+    fn blit_camera() {
+        let src = PixelView::new(Size::new(2, 2), (NV12::new(2, 2), [255, 0, 255, 0, 85, 255]));
+        let mut dst = PixelView::new(Size::new(2, 2), (format::RGB565, [0u8; 8]));
+
+        blit(&src, Rect::new(Point::new(0, 0), Size::new(2, 2)), &mut dst, Point::new(0, 0), BlitMode::Copy);
+
+        assert_eq!(dst.get(Point::new(0, 0)), RGB::new(248, 176, 176));
+        assert_eq!(dst.get(Point::new(1, 0)), RGB::new(176, 0, 0));
+    }
+
+    #[test]
+    fn nv12_stride() {
+        let fmt = NV12::new(2, 2).with_stride(4);
+        let mut view = PixelView::new_with_stride(Size::new(2, 2), fmt.stride, (fmt, [0u8; 12]));
+
+        view.set(Point::new(1, 1), YCbCr::new(1, 2, 3));
+        view.sub_view_mut(Rect::new(Point::new(0, 1), Size::new(1, 1)))
+            .set(Point::new(0, 0), YCbCr::new(4, 5, 6));
+
+        assert_eq!(view.get(Point::new(0, 0)), YCbCr::new(0, 5, 6));
+        assert_eq!(view.unwrap().1, [0, 0, 0, 0, 4, 1, 0, 0, 5, 6, 0, 0]);
+    }
+}
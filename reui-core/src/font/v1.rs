@@ -107,4 +107,38 @@ mod test {
             &[0, 1, 2, 3],
         );
     }
+
+    #[test]
+    fn fixed_dim() {
+        const fn q(value: i16) -> Q12_4 {
+            Q12_4::from_bits(value << 4)
+        }
+
+        static FONT: FontV1<format::BW1, Q12_4, u8, u8> = FontV1::new(
+            format::BW1,
+            &[b'0'..=b'1'],
+            &[
+                GlyphData::new(Rect::new(Point::new(q(0), q(-1)), Size::new(q(3), q(2))), 0),
+                GlyphData::new(Rect::new(Point::new(q(1), q(0)), Size::new(Q12_4::from_bits(40), q(2))), 1),
+            ],
+            &[0b0010_1101, 0b0000_0110],
+        );
+
+        let glyph = FONT.glyph('1').unwrap();
+
+        assert_eq!(FONT.glyph('2'), None);
+        assert_eq!(FONT.rect(glyph).size.w, Q12_4::from_bits(40));
+
+        let pixels = FONT.pixels(glyph);
+
+        assert_eq!(pixels.size(), Size::new(2, 2));
+        assert_eq!(pixels.get(Point::new(1, 0)), true.into());
+        assert_eq!(pixels.get(Point::new(0, 1)), true.into());
+        assert_eq!(pixels.get(Point::new(0, 0)), false.into());
+
+        let pixels = FONT.pixels(FONT.glyph('0').unwrap());
+
+        assert_eq!(pixels.size(), Size::new(3, 2));
+        assert_eq!(pixels.get(Point::new(2, 1)), true.into());
+    }
 }
@@ -2,8 +2,12 @@ mod point;
 mod size;
 mod area;
 mod rect;
+mod region;
+mod fixed;
 
 pub use self::point::*;
 pub use self::size::*;
 pub use self::area::*;
 pub use self::rect::*;
+pub use self::region::*;
+pub use self::fixed::*;
@@ -1,8 +1,9 @@
 use core::ops::{
+    Add, Sub, Div,
     BitAndAssign, BitAnd,
     BitOrAssign, BitOr,
 };
-use super::{Point, Size};
+use super::{Point, Size, Rect};
 
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub struct Area<Dim> {
@@ -24,6 +25,111 @@ impl<Dim> Area<Dim> {
     pub fn is_empty(&self) -> bool where Dim: PartialOrd {
         self.lt.x >= self.rb.x || self.lt.y >= self.rb.y
     }
+
+    /// Check that point is inside area
+    ///
+    /// The right bottom point is outside.
+    pub fn contains_point(&self, point: Point<Dim>) -> bool where Dim: PartialOrd {
+        self.lt.x <= point.x && point.x < self.rb.x &&
+            self.lt.y <= point.y && point.y < self.rb.y
+    }
+
+    /// Check that other area is fully inside area
+    ///
+    /// The empty area is inside any area.
+    pub fn contains(&self, other: &Area<Dim>) -> bool where Dim: PartialOrd {
+        other.is_empty() ||
+            self.lt.x <= other.lt.x && other.rb.x <= self.rb.x &&
+            self.lt.y <= other.lt.y && other.rb.y <= self.rb.y
+    }
+
+    /// Check that areas has common points
+    pub fn intersects(&self, other: &Area<Dim>) -> bool where Dim: PartialOrd + Copy {
+        !(*self & *other).is_empty()
+    }
+
+    /// Get common part of areas
+    pub fn intersection(&self, other: &Area<Dim>) -> Option<Area<Dim>> where Dim: PartialOrd + Copy {
+        let area = *self & *other;
+        if area.is_empty() {
+            None
+        } else {
+            Some(area)
+        }
+    }
+
+    /// Get area which covers both areas
+    ///
+    /// Unlike `|` the empty areas is ignored.
+    pub fn union(&self, other: &Area<Dim>) -> Area<Dim> where Dim: PartialOrd + Copy {
+        if other.is_empty() {
+            *self
+        } else if self.is_empty() {
+            *other
+        } else {
+            *self | *other
+        }
+    }
+
+    pub fn center(&self) -> Point<Dim>
+    where
+        Dim: Add<Output = Dim> + Div<Output = Dim> + From<u8> + Copy,
+    {
+        let two = Dim::from(2);
+        Point::new((self.lt.x + self.rb.x) / two, (self.lt.y + self.rb.y) / two)
+    }
+
+    /// Move area by delta
+    pub fn offset(&self, delta: Point<Dim>) -> Area<Dim> where Dim: Add<Output = Dim> + Copy {
+        Area::new(
+            Point::new(self.lt.x + delta.x, self.lt.y + delta.y),
+            Point::new(self.rb.x + delta.x, self.rb.y + delta.y),
+        )
+    }
+
+    /// Shrink area by specified distances from each side
+    ///
+    /// The area collapses when it is too small.
+    pub fn inset(&self, dx: Dim, dy: Dim) -> Area<Dim>
+    where
+        Dim: Add<Output = Dim> + Sub<Output = Dim> + PartialOrd + Default + Copy,
+    {
+        Rect::from(*self).inset(dx, dy).into()
+    }
+
+    /// Grow area by specified distances to each side
+    pub fn inflate(&self, dx: Dim, dy: Dim) -> Area<Dim>
+    where
+        Dim: Add<Output = Dim> + Sub<Output = Dim> + Copy,
+    {
+        Area::new(
+            Point::new(self.lt.x - dx, self.lt.y - dy),
+            Point::new(self.rb.x + dx, self.rb.y + dy),
+        )
+    }
+
+    /// Move point into area
+    ///
+    /// The right bottom point is treated as inside.
+    pub fn clamp(&self, point: Point<Dim>) -> Point<Dim> where Dim: PartialOrd + Copy {
+        Point::new(clamp(point.x, self.lt.x, self.rb.x), clamp(point.y, self.lt.y, self.rb.y))
+    }
+}
+
+pub(super) fn clamp<Dim: PartialOrd>(value: Dim, min: Dim, max: Dim) -> Dim {
+    if value < min {
+        min
+    } else if value > max {
+        max
+    } else {
+        value
+    }
+}
+
+impl<Dim> From<Rect<Dim>> for Area<Dim> where Dim: Add<Output = Dim> + Copy {
+    fn from(rect: Rect<Dim>) -> Self {
+        Self::new(rect.left_top(), rect.right_bottom())
+    }
 }
 
 impl<Dim> From<(Dim, Dim, Dim, Dim)> for Area<Dim> {
@@ -118,4 +224,58 @@ mod test {
         assert_eq!(Area::from((1, 2, 2, 3)) | Area::from((0, 1, 2, 3)), Area::from((0, 1, 2, 3)));
         assert_eq!(Area::from((1, 2, 3, 4)) | Area::from((2, 3, 2, 3)), Area::from((1, 2, 3, 4)));
     }
+
+    #[test]
+    fn contains() {
+        let area = Area::from((1, 2, 4, 5));
+
+        assert!(area.contains_point(Point::new(1, 2)));
+        assert!(area.contains_point(Point::new(3, 4)));
+        assert!(!area.contains_point(Point::new(4, 4)));
+        assert!(!area.contains_point(Point::new(0, 3)));
+
+        assert!(area.contains(&Area::from((1, 2, 4, 5))));
+        assert!(area.contains(&Area::from((2, 3, 3, 4))));
+        assert!(area.contains(&Area::from((7, 7, 7, 7))));
+        assert!(!area.contains(&Area::from((2, 3, 5, 4))));
+    }
+
+    #[test]
+    fn intersection() {
+        let area = Area::from((1, 2, 4, 5));
+
+        assert!(area.intersects(&Area::from((3, 4, 6, 6))));
+        assert!(!area.intersects(&Area::from((4, 2, 6, 5))));
+        assert_eq!(area.intersection(&Area::from((3, 0, 6, 3))), Some(Area::from((3, 2, 4, 3))));
+        assert_eq!(area.intersection(&Area::from((1, 5, 4, 6))), None);
+    }
+
+    #[test]
+    fn union() {
+        let area = Area::from((1, 2, 4, 5));
+
+        assert_eq!(area.union(&Area::from((3, 0, 6, 3))), Area::from((1, 0, 6, 5)));
+        assert_eq!(area.union(&Area::from((9, 9, 9, 9))), area);
+        assert_eq!(Area::from((9, 9, 0, 0)).union(&area), area);
+    }
+
+    #[test]
+    fn transform() {
+        let area = Area::from((1, 2, 5, 6));
+
+        assert_eq!(area.center(), Point::new(3, 4));
+        assert_eq!(area.offset(Point::new(-1, 2)), Area::from((0, 4, 4, 8)));
+        assert_eq!(area.inset(1, 1), Area::from((2, 3, 4, 5)));
+        assert_eq!(area.inset(3, 1), Area::from((4, 3, 4, 5)));
+        assert_eq!(area.inflate(1, 2), Area::from((0, 0, 6, 8)));
+        assert_eq!(area.clamp(Point::new(0, 9)), Point::new(1, 6));
+        assert_eq!(area.clamp(Point::new(3, 4)), Point::new(3, 4));
+    }
+
+    #[test]
+    fn rect() {
+        assert_eq!(Area::from(Rect::from((1, 2, 4, 6))), Area::from((1, 2, 4, 6)));
+        assert_eq!(Rect::from(Area::from((1, 2, 4, 6))), Rect::from((1, 2, 4, 6)));
+        assert_eq!(Rect::from(Area::from((4, 2, 1, 6))), Rect::new(Point::new(4, 2), Size::new(0, 0)));
+    }
 }
@@ -0,0 +1,427 @@
+use core::{
+    fmt,
+    ops::{
+        AddAssign, Add,
+        SubAssign, Sub,
+        MulAssign, Mul,
+        DivAssign, Div,
+        RemAssign, Rem,
+        Neg,
+    },
+};
+use num_traits::{AsPrimitive, PrimInt, Zero, One, WrappingAdd, WrappingSub, WrappingNeg};
+
+/// Integer type which can hold bits of fixed-point number
+pub trait FixedRepr: PrimInt + WrappingAdd + WrappingSub + WrappingNeg + Default + 'static {
+    /// Number of bits
+    const BITS: u32;
+
+    /// Integer type for intermediate results of multiplication and division
+    type Wide: PrimInt;
+
+    fn widen(self) -> Self::Wide;
+
+    fn narrow(wide: Self::Wide) -> Self;
+}
+
+macro_rules! fixed_repr {
+    ($($type: ty => $wide: ty,)*) => {
+        $(
+            impl FixedRepr for $type {
+                const BITS: u32 = <$type>::BITS;
+
+                type Wide = $wide;
+
+                fn widen(self) -> $wide {
+                    self as $wide
+                }
+
+                fn narrow(wide: $wide) -> Self {
+                    wide as Self
+                }
+            }
+        )*
+    };
+}
+
+fixed_repr! {
+    i8 => i16,
+    i16 => i32,
+    i32 => i64,
+    i64 => i128,
+    u8 => u16,
+    u16 => u32,
+    u32 => u64,
+    u64 => u128,
+}
+
+/// Fixed-point number with `FRAC` fractional bits
+///
+/// Allows sub-pixel coordinates on targets without FPU.
+/// The arithmetic operations wraps on overflow. The `FRAC` should be less than number of bits
+/// of representation, otherwise usage of number fails to compile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct Fixed<Repr, const FRAC: u32> {
+    bits: Repr,
+}
+
+/// Signed fixed-point number with 12 integer and 4 fractional bits
+pub type Q12_4 = Fixed<i16, 4>;
+
+/// Signed fixed-point number with 24 integer and 8 fractional bits
+pub type Q24_8 = Fixed<i32, 8>;
+
+/// Signed fixed-point number with 16 integer and 16 fractional bits
+pub type Q16_16 = Fixed<i32, 16>;
+
+impl<Repr, const FRAC: u32> Fixed<Repr, FRAC> {
+    /// Number of fractional bits
+    pub const FRAC_BITS: u32 = FRAC;
+
+    /// Create number from raw bits
+    pub const fn from_bits(bits: Repr) -> Self {
+        Self { bits }
+    }
+
+    /// Get raw bits of number
+    pub fn to_bits(self) -> Repr {
+        self.bits
+    }
+}
+
+impl<Repr: FixedRepr, const FRAC: u32> Fixed<Repr, FRAC> {
+    /// Number of fractional bits checked against number of bits of representation
+    const SHIFT: usize = {
+        assert!(FRAC < Repr::BITS, "Number of fractional bits should be less than number of bits");
+        FRAC as usize
+    };
+
+    /// Create number from integer
+    pub fn from_int(value: Repr) -> Self {
+        Self::from_bits(value << Self::SHIFT)
+    }
+
+    /// Get integer part (rounded toward negative infinity)
+    pub fn to_int(self) -> Repr {
+        self.bits >> Self::SHIFT
+    }
+
+    /// Get fractional part
+    pub fn frac(self) -> Self {
+        Self::from_bits(self.bits & Self::frac_mask())
+    }
+
+    /// Round toward negative infinity
+    pub fn floor(self) -> Self {
+        Self::from_bits(self.bits & !Self::frac_mask())
+    }
+
+    /// Round toward positive infinity
+    pub fn ceil(self) -> Self {
+        Self::from_bits(self.bits.wrapping_add(&Self::frac_mask())).floor()
+    }
+
+    /// Round to nearest integer (halves goes up)
+    pub fn round(self) -> Self {
+        Self::from_bits(self.bits.wrapping_add(&Self::half())).floor()
+    }
+
+    fn frac_mask() -> Repr {
+        (Repr::one() << Self::SHIFT).wrapping_sub(&Repr::one())
+    }
+
+    fn half() -> Repr {
+        if Self::SHIFT > 0 {
+            Repr::one() << (Self::SHIFT - 1)
+        } else {
+            Repr::zero()
+        }
+    }
+}
+
+impl<Repr: FixedRepr, const FRAC: u32> Zero for Fixed<Repr, FRAC> {
+    fn zero() -> Self {
+        Self::from_bits(Repr::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.bits.is_zero()
+    }
+}
+
+impl<Repr: FixedRepr, const FRAC: u32> One for Fixed<Repr, FRAC> {
+    fn one() -> Self {
+        Self::from_int(Repr::one())
+    }
+}
+
+impl<Repr, const FRAC: u32> From<u8> for Fixed<Repr, FRAC>
+where
+    Repr: FixedRepr,
+    u8: AsPrimitive<Repr>,
+{
+    fn from(value: u8) -> Self {
+        Self::from_int(value.as_())
+    }
+}
+
+impl<Repr: FixedRepr, const FRAC: u32> AddAssign for Fixed<Repr, FRAC> {
+    fn add_assign(&mut self, other: Self) {
+        self.bits = self.bits.wrapping_add(&other.bits);
+    }
+}
+
+impl<Repr: FixedRepr, const FRAC: u32> Add for Fixed<Repr, FRAC> {
+    type Output = Self;
+    fn add(mut self, other: Self) -> Self::Output {
+        self += other;
+        self
+    }
+}
+
+impl<Repr: FixedRepr, const FRAC: u32> SubAssign for Fixed<Repr, FRAC> {
+    fn sub_assign(&mut self, other: Self) {
+        self.bits = self.bits.wrapping_sub(&other.bits);
+    }
+}
+
+impl<Repr: FixedRepr, const FRAC: u32> Sub for Fixed<Repr, FRAC> {
+    type Output = Self;
+    fn sub(mut self, other: Self) -> Self::Output {
+        self -= other;
+        self
+    }
+}
+
+impl<Repr: FixedRepr, const FRAC: u32> MulAssign for Fixed<Repr, FRAC> {
+    fn mul_assign(&mut self, other: Self) {
+        self.bits = Repr::narrow((self.bits.widen() * other.bits.widen()) >> Self::SHIFT);
+    }
+}
+
+impl<Repr: FixedRepr, const FRAC: u32> Mul for Fixed<Repr, FRAC> {
+    type Output = Self;
+    fn mul(mut self, other: Self) -> Self::Output {
+        self *= other;
+        self
+    }
+}
+
+impl<Repr: FixedRepr, const FRAC: u32> DivAssign for Fixed<Repr, FRAC> {
+    fn div_assign(&mut self, other: Self) {
+        self.bits = Repr::narrow((self.bits.widen() << Self::SHIFT) / other.bits.widen());
+    }
+}
+
+impl<Repr: FixedRepr, const FRAC: u32> Div for Fixed<Repr, FRAC> {
+    type Output = Self;
+    fn div(mut self, other: Self) -> Self::Output {
+        self /= other;
+        self
+    }
+}
+
+impl<Repr: FixedRepr, const FRAC: u32> RemAssign for Fixed<Repr, FRAC> {
+    fn rem_assign(&mut self, other: Self) {
+        // the remainder of division by -1 is always zero, but it overflows for minimum value
+        self.bits = if Repr::min_value() < Repr::zero() && other.bits == Repr::zero().wrapping_sub(&Repr::one()) {
+            Repr::zero()
+        } else {
+            self.bits % other.bits
+        };
+    }
+}
+
+impl<Repr: FixedRepr, const FRAC: u32> Rem for Fixed<Repr, FRAC> {
+    type Output = Self;
+    fn rem(mut self, other: Self) -> Self::Output {
+        self %= other;
+        self
+    }
+}
+
+impl<Repr, const FRAC: u32> Neg for Fixed<Repr, FRAC>
+where
+    Repr: FixedRepr + Neg<Output = Repr>,
+{
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self::from_bits(self.bits.wrapping_neg())
+    }
+}
+
+impl<Repr, const FRAC: u32> fmt::Display for Fixed<Repr, FRAC>
+where
+    Repr: FixedRepr + AsPrimitive<f64>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value: f64 = self.as_();
+        fmt::Display::fmt(&value, f)
+    }
+}
+
+impl<Repr, const FRAC: u32, ToRepr, const TO_FRAC: u32> AsPrimitive<Fixed<ToRepr, TO_FRAC>> for Fixed<Repr, FRAC>
+where
+    Repr: FixedRepr + AsPrimitive<ToRepr>,
+    ToRepr: FixedRepr,
+{
+    fn as_(self) -> Fixed<ToRepr, TO_FRAC> {
+        // shift before narrowing and after widening to keep integer part
+        let (from, to) = (Self::SHIFT, Fixed::<ToRepr, TO_FRAC>::SHIFT);
+
+        Fixed::from_bits(if to < from {
+            (self.bits >> (from - to)).as_()
+        } else {
+            self.bits.as_() << (to - from)
+        })
+    }
+}
+
+macro_rules! fixed_as_int {
+    ($($type: ty),*) => {
+        $(
+            impl<Repr, const FRAC: u32> AsPrimitive<$type> for Fixed<Repr, FRAC>
+            where
+                Repr: FixedRepr + AsPrimitive<$type>,
+            {
+                /// Integer part is taken (rounded toward negative infinity)
+                fn as_(self) -> $type {
+                    self.to_int().as_()
+                }
+            }
+
+            impl<Repr, const FRAC: u32> AsPrimitive<Fixed<Repr, FRAC>> for $type
+            where
+                Repr: FixedRepr,
+                $type: AsPrimitive<Repr>,
+            {
+                fn as_(self) -> Fixed<Repr, FRAC> {
+                    Fixed::from_int(self.as_())
+                }
+            }
+        )*
+    };
+}
+
+fixed_as_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+macro_rules! fixed_as_float {
+    ($($type: ty),*) => {
+        $(
+            impl<Repr, const FRAC: u32> AsPrimitive<$type> for Fixed<Repr, FRAC>
+            where
+                Repr: FixedRepr + AsPrimitive<$type>,
+            {
+                fn as_(self) -> $type {
+                    let bits: $type = self.bits.as_();
+                    bits / (1u64 << Self::SHIFT) as $type
+                }
+            }
+
+            impl<Repr, const FRAC: u32> AsPrimitive<Fixed<Repr, FRAC>> for $type
+            where
+                Repr: FixedRepr,
+                $type: AsPrimitive<Repr>,
+            {
+                /// Value is rounded to nearest
+                fn as_(self) -> Fixed<Repr, FRAC> {
+                    let bits = self * (1u64 << Fixed::<Repr, FRAC>::SHIFT) as $type;
+                    Fixed::from_bits(if bits < 0.0 { bits - 0.5 } else { bits + 0.5 }.as_())
+                }
+            }
+        )*
+    };
+}
+
+fixed_as_float!(f32, f64);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Point, Size, Rect};
+
+    fn q(value: f32) -> Q12_4 {
+        value.as_()
+    }
+
+    #[test]
+    fn convert() {
+        assert_eq!(Q12_4::from_int(3).to_bits(), 48);
+        assert_eq!(q(1.5).to_bits(), 24);
+        assert_eq!(q(-0.25).to_bits(), -4);
+        assert_eq!(AsPrimitive::<f32>::as_(q(-2.75)), -2.75);
+        assert_eq!(AsPrimitive::<i32>::as_(q(-2.75)), -3);
+        assert_eq!(AsPrimitive::<usize>::as_(q(7.5)), 7);
+        assert_eq!(AsPrimitive::<Q12_4>::as_(5u8), q(5.0));
+        assert_eq!(AsPrimitive::<Q16_16>::as_(q(-1.25)), Q16_16::from_bits(-5 << 14));
+        assert_eq!(AsPrimitive::<Q12_4>::as_(Q16_16::from_bits(0x18000)), q(1.5));
+        assert_eq!(Q12_4::from(2u8), q(2.0));
+        assert_eq!(AsPrimitive::<f64>::as_(Fixed::<i64, 63>::from_bits(1 << 62)), 0.5);
+        assert_eq!(AsPrimitive::<Fixed<u8, 7>>::as_(0.75f32).to_bits(), 96);
+        assert_eq!(Fixed::<i8, 7>::from_bits(-64).round(), Fixed::from_bits(0));
+    }
+
+    #[test]
+    fn rounding() {
+        assert_eq!(q(1.25).floor(), q(1.0));
+        assert_eq!(q(-1.25).floor(), q(-2.0));
+        assert_eq!(q(1.25).ceil(), q(2.0));
+        assert_eq!(q(-1.25).ceil(), q(-1.0));
+        assert_eq!(q(1.5).round(), q(2.0));
+        assert_eq!(q(1.25).round(), q(1.0));
+        assert_eq!(q(-1.75).frac(), q(0.25));
+    }
+
+    #[test]
+    fn ops() {
+        assert_eq!(q(1.5) + q(0.75), q(2.25));
+        assert_eq!(q(1.5) - q(1.75), q(-0.25));
+        assert_eq!(q(1.5) * q(-2.5), q(-3.75));
+        assert_eq!(q(3.0) / q(4.0), q(0.75));
+        assert_eq!(q(3.5) % q(1.0), q(0.5));
+        assert_eq!(-q(1.5), q(-1.5));
+        assert!(q(-0.5) < q(0.25));
+        assert!(Q12_4::zero().is_zero());
+        assert_eq!(Q12_4::from_bits(i16::MAX) + Q12_4::from_bits(1), Q12_4::from_bits(i16::MIN));
+        assert_eq!(Q12_4::from_bits(i16::MIN) - Q12_4::from_bits(1), Q12_4::from_bits(i16::MAX));
+        assert_eq!(-Q12_4::from_bits(i16::MIN), Q12_4::from_bits(i16::MIN));
+        assert_eq!(Q12_4::from_bits(i16::MIN) % Q12_4::from_bits(-1), Q12_4::zero());
+        assert_eq!(Q12_4::one(), q(1.0));
+        assert_eq!(Fixed::<u8, 0>::from_int(5) % Fixed::from_int(255), Fixed::from_int(5));
+        assert_eq!(Fixed::<u8, 4>::from_int(3) % Fixed::from_bits(u8::MAX), Fixed::from_int(3));
+    }
+
+    #[test]
+    fn display() {
+        use core::fmt::Write;
+
+        struct Buf([u8; 16], usize);
+
+        impl Write for Buf {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                self.0[self.1..self.1 + s.len()].copy_from_slice(s.as_bytes());
+                self.1 += s.len();
+                Ok(())
+            }
+        }
+
+        let mut buf = Buf([0; 16], 0);
+        write!(buf, "{}", q(-2.75)).unwrap();
+
+        assert_eq!(&buf.0[..buf.1], b"-2.75");
+    }
+
+    #[test]
+    fn geometry() {
+        let rect = Rect::new(Point::new(q(0.5), q(1.0)), Size::new(q(2.0), q(1.5)));
+
+        assert_eq!(rect.right_bottom(), Point::new(q(2.5), q(2.5)));
+        assert_eq!(rect.center(), Point::new(q(1.5), q(1.75)));
+        assert!(rect.contains_point(Point::new(q(2.25), q(1.0))));
+        assert_eq!(rect.size().area(), q(3.0));
+        assert_eq!(rect.size().as_::<usize>(), Size::new(2, 1));
+        assert_eq!(rect.inset(q(0.25), q(1.0)), Rect::new(Point::new(q(0.75), q(2.0)), Size::new(q(1.5), q(0.0))));
+        assert_eq!(-rect.left_top() * q(2.0), Point::new(q(-1.0), q(-2.0)));
+    }
+}
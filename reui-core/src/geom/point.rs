@@ -1,6 +1,9 @@
 use core::ops::{
     AddAssign, Add,
     SubAssign, Sub,
+    MulAssign, Mul,
+    DivAssign, Div,
+    Neg,
 };
 use super::{Size};
 
@@ -67,3 +70,71 @@ impl<Dim> Sub<Point<Dim>> for Point<Dim> where Dim: Sub<Output = Dim> {
         Size { w: self.x - other.x, h: self.y - other.y }
     }
 }
+
+impl<Dim> AddAssign<Point<Dim>> for Point<Dim> where Dim: AddAssign {
+    fn add_assign(&mut self, other: Point<Dim>) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<Dim> Add<Point<Dim>> for Point<Dim> where Dim: AddAssign {
+    type Output = Point<Dim>;
+    fn add(mut self, other: Point<Dim>) -> Self::Output {
+        self += other;
+        self
+    }
+}
+
+impl<Dim> Neg for Point<Dim> where Dim: Neg<Output = Dim> {
+    type Output = Point<Dim>;
+    fn neg(self) -> Self::Output {
+        Point { x: -self.x, y: -self.y }
+    }
+}
+
+impl<Dim> MulAssign<Dim> for Point<Dim> where Dim: MulAssign + Copy {
+    fn mul_assign(&mut self, factor: Dim) {
+        self.x *= factor;
+        self.y *= factor;
+    }
+}
+
+impl<Dim> Mul<Dim> for Point<Dim> where Dim: MulAssign + Copy {
+    type Output = Point<Dim>;
+    fn mul(mut self, factor: Dim) -> Self::Output {
+        self *= factor;
+        self
+    }
+}
+
+impl<Dim> DivAssign<Dim> for Point<Dim> where Dim: DivAssign + Copy {
+    fn div_assign(&mut self, divisor: Dim) {
+        self.x /= divisor;
+        self.y /= divisor;
+    }
+}
+
+impl<Dim> Div<Dim> for Point<Dim> where Dim: DivAssign + Copy {
+    type Output = Point<Dim>;
+    fn div(mut self, divisor: Dim) -> Self::Output {
+        self /= divisor;
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ops() {
+        assert_eq!(Point::new(1, 2) + Point::new(3, 4), Point::new(4, 6));
+        assert_eq!(Point::new(1, 2) + Size::new(3, 4), Point::new(4, 6));
+        assert_eq!(Point::new(1, 2) - Size::new(3, 4), Point::new(-2, -2));
+        assert_eq!(Point::new(5, 7) - Point::new(1, 2), Size::new(4, 5));
+        assert_eq!(-Point::new(1, -2), Point::new(-1, 2));
+        assert_eq!(Point::new(1, -2) * 3, Point::new(3, -6));
+        assert_eq!(Point::new(6, -9) / 3, Point::new(2, -3));
+    }
+}
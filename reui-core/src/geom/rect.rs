@@ -1,5 +1,9 @@
-use core::ops::{Add, Sub};
-use super::{Point, Size};
+use core::ops::{
+    Add, Sub, Div,
+    BitAndAssign, BitAnd,
+    BitOrAssign, BitOr,
+};
+use super::{Point, Size, Area, area::clamp};
 
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub struct Rect<Dim> {
@@ -97,6 +101,129 @@ impl<Dim> Rect<Dim> {
     {
         Point::new(point.x + self.point.x, point.y + self.point.y)
     }
+
+    /// Check that point is inside rectangle
+    ///
+    /// The right and bottom edges is outside.
+    pub fn contains_point(&self, point: Point<Dim>) -> bool
+    where
+        Dim: Add<Output = Dim> + PartialOrd + Copy,
+    {
+        Area::from(*self).contains_point(point)
+    }
+
+    /// Check that other rectangle is fully inside rectangle
+    ///
+    /// The collapsed rectangle is inside any rectangle.
+    pub fn contains(&self, other: &Rect<Dim>) -> bool
+    where
+        Dim: Add<Output = Dim> + PartialOrd + Copy,
+    {
+        Area::from(*self).contains(&Area::from(*other))
+    }
+
+    /// Check that rectangles has common points
+    pub fn intersects(&self, other: &Rect<Dim>) -> bool
+    where
+        Dim: Add<Output = Dim> + PartialOrd + Copy,
+    {
+        Area::from(*self).intersects(&Area::from(*other))
+    }
+
+    /// Get common part of rectangles
+    pub fn intersection(&self, other: &Rect<Dim>) -> Option<Rect<Dim>>
+    where
+        Dim: Add<Output = Dim> + Sub<Output = Dim> + PartialOrd + Copy,
+    {
+        Area::from(*self).intersection(&Area::from(*other))
+            .map(|area| Rect::new(area.lt, area.size()))
+    }
+
+    /// Get rectangle which covers both rectangles
+    ///
+    /// The collapsed rectangles is ignored.
+    pub fn union(&self, other: &Rect<Dim>) -> Rect<Dim>
+    where
+        Dim: Add<Output = Dim> + Sub<Output = Dim> + PartialOrd + Default + Copy,
+    {
+        Area::from(*self).union(&Area::from(*other)).into()
+    }
+
+    pub fn center(&self) -> Point<Dim>
+    where
+        Dim: Add<Output = Dim> + Div<Output = Dim> + From<u8> + Copy,
+    {
+        let two = Dim::from(2);
+        Point::new(self.point.x + self.size.w / two, self.point.y + self.size.h / two)
+    }
+
+    /// Move rectangle by delta
+    pub fn offset(&self, delta: Point<Dim>) -> Rect<Dim>
+    where
+        Dim: Add<Output = Dim> + Copy,
+    {
+        Rect::new(self.to_global(delta), self.size)
+    }
+
+    /// Shrink rectangle by specified distances from each side
+    ///
+    /// The rectangle collapses when it is too small.
+    pub fn inset(&self, dx: Dim, dy: Dim) -> Rect<Dim>
+    where
+        Dim: Add<Output = Dim> + Sub<Output = Dim> + PartialOrd + Default + Copy,
+    {
+        let (x, w) = inset(self.point.x, self.size.w, dx);
+        let (y, h) = inset(self.point.y, self.size.h, dy);
+
+        Rect::new(Point::new(x, y), Size::new(w, h))
+    }
+
+    /// Grow rectangle by specified distances to each side
+    pub fn inflate(&self, dx: Dim, dy: Dim) -> Rect<Dim>
+    where
+        Dim: Add<Output = Dim> + Sub<Output = Dim> + Copy,
+    {
+        Rect::new(
+            Point::new(self.point.x - dx, self.point.y - dy),
+            Size::new(self.size.w + dx + dx, self.size.h + dy + dy),
+        )
+    }
+
+    /// Move point into rectangle
+    ///
+    /// The right and bottom edges is treated as inside.
+    pub fn clamp(&self, point: Point<Dim>) -> Point<Dim>
+    where
+        Dim: Add<Output = Dim> + PartialOrd + Copy,
+    {
+        Area::from(*self).clamp(point)
+    }
+}
+
+/// Shrink segment from both sides
+fn inset<Dim>(start: Dim, len: Dim, delta: Dim) -> (Dim, Dim)
+where
+    Dim: Add<Output = Dim> + Sub<Output = Dim> + PartialOrd + Default + Copy,
+{
+    if len > delta + delta {
+        (start + delta, len - delta - delta)
+    } else {
+        (clamp(start + delta, start, start + len), Dim::default())
+    }
+}
+
+impl<Dim> From<Area<Dim>> for Rect<Dim>
+where
+    Dim: Sub<Output = Dim> + PartialOrd + Default + Copy,
+{
+    /// The empty area gives collapsed rectangle at left top point
+    fn from(area: Area<Dim>) -> Self {
+        if area.is_empty() {
+            Self::new(area.lt, Size::default())
+        } else {
+            Self::new(area.lt, area.size())
+        }
+    }
 }
 
 impl<Dim> From<(Dim, Dim, Dim, Dim)> for Rect<Dim>
@@ -116,3 +243,104 @@ where
         Self::new(Point::new(ltrb[0], ltrb[1]), Size::new(ltrb[2] - ltrb[0], ltrb[3] - ltrb[1]))
     }
 }
+
+impl<Dim> BitAndAssign<Rect<Dim>> for Rect<Dim>
+where
+    Dim: Add<Output = Dim> + Sub<Output = Dim> + PartialOrd + Default + Copy,
+{
+    fn bitand_assign(&mut self, other: Rect<Dim>) {
+        *self = (Area::from(*self) & Area::from(other)).into();
+    }
+}
+
+impl<Dim> BitAnd<Rect<Dim>> for Rect<Dim>
+where
+    Dim: Add<Output = Dim> + Sub<Output = Dim> + PartialOrd + Default + Copy,
+{
+    type Output = Rect<Dim>;
+
+    fn bitand(mut self, other: Rect<Dim>) -> Self::Output {
+        self &= other;
+        self
+    }
+}
+
+impl<Dim> BitOrAssign<Rect<Dim>> for Rect<Dim>
+where
+    Dim: Add<Output = Dim> + Sub<Output = Dim> + PartialOrd + Default + Copy,
+{
+    fn bitor_assign(&mut self, other: Rect<Dim>) {
+        *self = self.union(&other);
+    }
+}
+
+impl<Dim> BitOr<Rect<Dim>> for Rect<Dim>
+where
+    Dim: Add<Output = Dim> + Sub<Output = Dim> + PartialOrd + Default + Copy,
+{
+    type Output = Rect<Dim>;
+
+    fn bitor(mut self, other: Rect<Dim>) -> Self::Output {
+        self |= other;
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn edges() {
+        let rect = Rect::from((1, 2, 4, 6));
+
+        assert_eq!(rect.size(), Size::new(3, 4));
+        assert_eq!(rect.right_bottom(), Point::new(4, 6));
+        assert_eq!(rect.to_local(Point::new(2, 2)), Point::new(1, 0));
+        assert_eq!(rect.to_global(Point::new(1, 0)), Point::new(2, 2));
+    }
+
+    #[test]
+    fn contains() {
+        let rect = Rect::from((1, 2, 4, 5));
+
+        assert!(rect.contains_point(Point::new(1, 2)));
+        assert!(!rect.contains_point(Point::new(1, 5)));
+        assert!(rect.contains(&Rect::from((2, 3, 4, 5))));
+        assert!(rect.contains(&Rect::from((9, 9, 9, 9))));
+        assert!(!rect.contains(&Rect::from((0, 3, 2, 4))));
+    }
+
+    #[test]
+    fn and() {
+        let rect = Rect::from((1, 2, 4, 5));
+
+        assert!(rect.intersects(&Rect::from((3, 4, 6, 6))));
+        assert!(!rect.intersects(&Rect::from((4, 2, 6, 5))));
+        assert_eq!(rect.intersection(&Rect::from((3, 0, 6, 3))), Some(Rect::from((3, 2, 4, 3))));
+        assert_eq!(rect.intersection(&Rect::from((4, 2, 6, 5))), None);
+        assert_eq!(rect & Rect::from((3, 0, 6, 3)), Rect::from((3, 2, 4, 3)));
+        assert!((rect & Rect::from((4, 2, 6, 5))).is_collapsed());
+    }
+
+    #[test]
+    fn or() {
+        let rect = Rect::from((1, 2, 4, 5));
+
+        assert_eq!(rect | Rect::from((3, 0, 6, 3)), Rect::from((1, 0, 6, 5)));
+        assert_eq!(rect | Rect::from((9, 9, 9, 9)), rect);
+        assert_eq!(Rect::from((0, 0, 0, 0)).union(&rect), rect);
+    }
+
+    #[test]
+    fn transform() {
+        let rect = Rect::from((1, 2, 5, 6));
+
+        assert_eq!(rect.center(), Point::new(3, 4));
+        assert_eq!(rect.offset(Point::new(-1, 2)), Rect::from((0, 4, 4, 8)));
+        assert_eq!(rect.inset(1, 1), Rect::from((2, 3, 4, 5)));
+        assert_eq!(rect.inset(3, 9), Rect::from((4, 6, 4, 6)));
+        assert_eq!(rect.inflate(1, 2), Rect::from((0, 0, 6, 8)));
+        assert_eq!(rect.clamp(Point::new(0, 9)), Point::new(1, 6));
+    }
+}
@@ -0,0 +1,410 @@
+use core::slice::Iter;
+use super::{Point, Area};
+
+/// Storage of region areas
+pub trait RegionBuf {
+    /// The type of coordinates
+    type Dim;
+
+    /// Get stored areas
+    fn areas(&self) -> &[Area<Self::Dim>];
+
+    /// Add area to the end
+    ///
+    /// Returns `false` when storage is full.
+    fn push(&mut self, area: Area<Self::Dim>) -> bool;
+
+    /// Remove area keeping order of other areas
+    fn remove(&mut self, index: usize) -> Area<Self::Dim>;
+
+    /// Remove all areas
+    fn clear(&mut self);
+}
+
+/// Fixed-capacity storage of areas
+#[derive(Debug, Clone, Copy)]
+pub struct AreaArray<Dim, const N: usize> {
+    areas: [Area<Dim>; N],
+    len: usize,
+}
+
+impl<Dim, const N: usize> Default for AreaArray<Dim, N>
+where
+    Dim: Default + Copy,
+{
+    fn default() -> Self {
+        Self { areas: [Area::default(); N], len: 0 }
+    }
+}
+
+impl<Dim, const N: usize> RegionBuf for AreaArray<Dim, N>
+where
+    Dim: Copy,
+{
+    type Dim = Dim;
+
+    fn areas(&self) -> &[Area<Dim>] {
+        &self.areas[..self.len]
+    }
+
+    fn push(&mut self, area: Area<Dim>) -> bool {
+        if self.len == N {
+            return false;
+        }
+
+        self.areas[self.len] = area;
+        self.len += 1;
+        true
+    }
+
+    fn remove(&mut self, index: usize) -> Area<Dim> {
+        let area = self.areas[index];
+        self.areas.copy_within(index + 1..self.len, index);
+        self.len -= 1;
+        area
+    }
+
+    fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+#[cfg(any(feature = "alloc", test))]
+impl<Dim> RegionBuf for alloc::vec::Vec<Area<Dim>> {
+    type Dim = Dim;
+
+    fn areas(&self) -> &[Area<Dim>] {
+        self
+    }
+
+    fn push(&mut self, area: Area<Dim>) -> bool {
+        alloc::vec::Vec::push(self, area);
+        true
+    }
+
+    fn remove(&mut self, index: usize) -> Area<Dim> {
+        alloc::vec::Vec::remove(self, index)
+    }
+
+    fn clear(&mut self) {
+        alloc::vec::Vec::clear(self)
+    }
+}
+
+/// Get up to four disjoint areas which covers `a` excluding `b`
+fn subtract<Dim: PartialOrd + Copy>(a: &Area<Dim>, b: &Area<Dim>) -> [Option<Area<Dim>>; 4] {
+    if !a.intersects(b) {
+        return [Some(*a), None, None, None];
+    }
+
+    let max = |a: Dim, b: Dim| if a > b { a } else { b };
+    let min = |a: Dim, b: Dim| if a < b { a } else { b };
+
+    // vertical range of middle band
+    let (top, bottom) = (max(a.lt.y, b.lt.y), min(a.rb.y, b.rb.y));
+    let area = |l, t, r, b| Some(Area::from((l, t, r, b))).filter(|area: &Area<Dim>| !area.is_empty());
+
+    [
+        area(a.lt.x, a.lt.y, a.rb.x, b.lt.y),
+        area(a.lt.x, b.rb.y, a.rb.x, a.rb.y),
+        area(a.lt.x, top, b.lt.x, bottom),
+        area(b.rb.x, top, a.rb.x, bottom),
+    ]
+}
+
+/// Set of disjoint areas
+///
+/// When capacity of storage is exceeded the region is approximated by bounding area,
+/// so the result always covers exact one.
+#[derive(Debug, Clone, Default)]
+pub struct Region<Buf> {
+    buf: Buf,
+}
+
+/// Region with fixed capacity
+pub type RegionArray<Dim, const N: usize> = Region<AreaArray<Dim, N>>;
+
+/// Region with heap-allocated storage
+#[cfg(any(feature = "alloc", test))]
+pub type RegionVec<Dim> = Region<alloc::vec::Vec<Area<Dim>>>;
+
+impl<Buf> Region<Buf>
+where
+    Buf: RegionBuf,
+    Buf::Dim: PartialOrd + Copy,
+{
+    /// Create empty region using storage
+    pub fn new(mut buf: Buf) -> Self {
+        buf.clear();
+        Self { buf }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buf.areas().is_empty()
+    }
+
+    /// Get disjoint areas of region
+    pub fn areas(&self) -> &[Area<Buf::Dim>] {
+        self.buf.areas()
+    }
+
+    pub fn iter(&self) -> Iter<'_, Area<Buf::Dim>> {
+        self.buf.areas().iter()
+    }
+
+    pub fn clear(&mut self) {
+        self.buf.clear();
+    }
+
+    /// Get bounding area of region
+    pub fn bounds(&self) -> Option<Area<Buf::Dim>> {
+        let mut areas = self.iter().cloned();
+        let first = areas.next()?;
+
+        Some(areas.fold(first, |bounds, area| bounds | area))
+    }
+
+    /// Check that point is inside of region
+    pub fn contains_point(&self, point: Point<Buf::Dim>) -> bool {
+        self.iter().any(|area| {
+            area.lt.x <= point.x && point.x < area.rb.x && area.lt.y <= point.y && point.y < area.rb.y
+        })
+    }
+
+    /// Check that area is fully covered by region
+    pub fn contains_area(&self, area: Area<Buf::Dim>) -> bool {
+        area.is_empty() || self.covers(area, 0)
+    }
+
+    fn covers(&self, area: Area<Buf::Dim>, start: usize) -> bool {
+        let areas = self.buf.areas();
+
+        match (start..areas.len()).find(|index| areas[*index].intersects(&area)) {
+            Some(index) => subtract(&area, &areas[index]).iter().flatten()
+                .all(|piece| self.covers(*piece, index + 1)),
+            None => false,
+        }
+    }
+
+    /// Add area to region
+    pub fn union_area(&mut self, area: Area<Buf::Dim>) {
+        let mut overflow = None;
+
+        if !area.is_empty() {
+            self.add_disjoint(area, 0, &mut overflow);
+        }
+
+        self.collapse(overflow);
+    }
+
+    fn add_disjoint(&mut self, area: Area<Buf::Dim>, start: usize, overflow: &mut Option<Area<Buf::Dim>>) {
+        let areas = self.buf.areas();
+
+        match (start..areas.len()).find(|index| areas[*index].intersects(&area)) {
+            Some(index) => {
+                let other = areas[index];
+
+                for piece in subtract(&area, &other).iter().flatten() {
+                    self.add_disjoint(*piece, index + 1, overflow);
+                }
+            }
+            None => self.push(area, overflow),
+        }
+    }
+
+    /// Remove area from region
+    pub fn subtract_area(&mut self, area: Area<Buf::Dim>) {
+        let mut overflow = None;
+        let mut index = 0;
+
+        while index < self.buf.areas().len() {
+            let other = self.buf.areas()[index];
+
+            if other.intersects(&area) {
+                self.buf.remove(index);
+
+                for piece in subtract(&other, &area).iter().flatten() {
+                    self.push(*piece, &mut overflow);
+                }
+            } else {
+                index += 1;
+            }
+        }
+
+        self.collapse(overflow);
+    }
+
+    /// Keep only part of region inside of area
+    pub fn intersect_area(&mut self, area: Area<Buf::Dim>) {
+        for _ in 0..self.buf.areas().len() {
+            let other = self.buf.remove(0);
+
+            if other.intersects(&area) {
+                // the intersection is smaller so it always fits
+                self.buf.push(other & area);
+            }
+        }
+    }
+
+    /// Add other region
+    pub fn union<OtherBuf>(&mut self, other: &Region<OtherBuf>)
+    where
+        OtherBuf: RegionBuf<Dim = Buf::Dim>,
+    {
+        for area in other.buf.areas() {
+            self.union_area(*area);
+        }
+    }
+
+    /// Remove other region
+    pub fn subtract<OtherBuf>(&mut self, other: &Region<OtherBuf>)
+    where
+        OtherBuf: RegionBuf<Dim = Buf::Dim>,
+    {
+        for area in other.buf.areas() {
+            self.subtract_area(*area);
+        }
+    }
+
+    /// Keep only part of region inside of other region
+    pub fn intersect<OtherBuf>(&mut self, other: &Region<OtherBuf>)
+    where
+        OtherBuf: RegionBuf<Dim = Buf::Dim>,
+    {
+        let mut overflow = None;
+
+        for _ in 0..self.buf.areas().len() {
+            let area = self.buf.remove(0);
+
+            for other in other.buf.areas() {
+                if area.intersects(other) {
+                    self.push(area & *other, &mut overflow);
+                }
+            }
+        }
+
+        self.collapse(overflow);
+    }
+
+    fn push(&mut self, area: Area<Buf::Dim>, overflow: &mut Option<Area<Buf::Dim>>) {
+        if !self.buf.push(area) {
+            *overflow = Some(match *overflow {
+                Some(bounds) => bounds | area,
+                None => area,
+            });
+        }
+    }
+
+    /// Replace region by bounding area when some areas was not fit
+    fn collapse(&mut self, overflow: Option<Area<Buf::Dim>>) {
+        if let Some(area) = overflow {
+            let bounds = self.bounds().map_or(area, |bounds| bounds | area);
+
+            self.buf.clear();
+            self.buf.push(bounds);
+        }
+    }
+}
+
+impl<'a, Buf> IntoIterator for &'a Region<Buf>
+where
+    Buf: RegionBuf,
+    Buf::Dim: PartialOrd + Copy,
+{
+    type Item = &'a Area<Buf::Dim>;
+    type IntoIter = Iter<'a, Area<Buf::Dim>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn pixels<Buf: RegionBuf<Dim = i32>>(region: &Region<Buf>) -> i32 {
+        region.iter().map(|area| area.size().w * area.size().h).sum()
+    }
+
+    fn disjoint<Buf: RegionBuf<Dim = i32>>(region: &Region<Buf>) -> bool {
+        let areas = region.areas();
+        (0..areas.len()).all(|i| (i + 1..areas.len()).all(|j| !areas[i].intersects(&areas[j])))
+    }
+
+    #[test]
+    fn union() {
+        let mut region = RegionVec::default();
+
+        region.union_area(Area::from((0, 0, 4, 4)));
+        region.union_area(Area::from((2, 2, 6, 6)));
+        region.union_area(Area::from((1, 1, 2, 2)));
+
+        assert!(disjoint(&region));
+        assert_eq!(pixels(&region), 28);
+        assert_eq!(region.bounds(), Some(Area::from((0, 0, 6, 6))));
+        assert!(region.contains_point(Point::new(5, 5)));
+        assert!(!region.contains_point(Point::new(5, 0)));
+        assert!(region.contains_area(Area::from((2, 2, 5, 5))));
+        assert!(!region.contains_area(Area::from((0, 0, 5, 5))));
+    }
+
+    #[test]
+    fn subtract() {
+        let mut region = RegionVec::default();
+
+        region.union_area(Area::from((0, 0, 4, 4)));
+        region.subtract_area(Area::from((1, 1, 3, 3)));
+
+        assert!(disjoint(&region));
+        assert_eq!(pixels(&region), 12);
+        assert!(!region.contains_point(Point::new(2, 2)));
+
+        let mut other = RegionVec::default();
+        other.union_area(Area::from((0, 0, 4, 1)));
+        region.subtract(&other);
+
+        assert_eq!(pixels(&region), 8);
+    }
+
+    #[test]
+    fn intersect() {
+        let mut region = RegionVec::default();
+
+        region.union_area(Area::from((0, 0, 4, 4)));
+        region.union_area(Area::from((4, 0, 8, 2)));
+
+        let mut other = RegionVec::new(Vec::new());
+        other.union_area(Area::from((2, 1, 6, 3)));
+        other.union_area(Area::from((7, 0, 9, 1)));
+
+        region.intersect(&other);
+
+        assert!(disjoint(&region));
+        assert_eq!(pixels(&region), 4 + 2 + 1);
+
+        region.intersect_area(Area::from((0, 0, 3, 9)));
+
+        assert!(region.iter().eq([Area::from((2, 1, 3, 3))].iter()));
+    }
+
+    #[test]
+    fn fixed() {
+        let mut region = RegionArray::<i32, 2>::default();
+
+        region.union_area(Area::from((0, 0, 2, 2)));
+        region.union_area(Area::from((4, 4, 6, 6)));
+
+        assert_eq!(region.areas().len(), 2);
+
+        // does not fit so region is approximated
+        region.union_area(Area::from((8, 0, 9, 1)));
+
+        assert!(region.iter().eq([Area::from((0, 0, 9, 6))].iter()));
+
+        region.subtract_area(Area::from((3, 0, 9, 9)));
+
+        assert!(region.iter().eq([Area::from((0, 0, 3, 6))].iter()));
+    }
+}
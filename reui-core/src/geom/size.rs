@@ -1,4 +1,9 @@
-use core::ops::{Mul};
+use core::ops::{
+    AddAssign, Add,
+    SubAssign, Sub,
+    MulAssign, Mul,
+    DivAssign, Div,
+};
 use num_traits::AsPrimitive;
 
 /// The size type
@@ -51,3 +56,78 @@ where Dim: Copy
         Self::new(wh[0], wh[1])
     }
 }
+
+impl<Dim> AddAssign<Size<Dim>> for Size<Dim> where Dim: AddAssign {
+    fn add_assign(&mut self, other: Size<Dim>) {
+        self.w += other.w;
+        self.h += other.h;
+    }
+}
+
+impl<Dim> Add<Size<Dim>> for Size<Dim> where Dim: AddAssign {
+    type Output = Size<Dim>;
+    fn add(mut self, other: Size<Dim>) -> Self::Output {
+        self += other;
+        self
+    }
+}
+
+impl<Dim> SubAssign<Size<Dim>> for Size<Dim> where Dim: SubAssign {
+    fn sub_assign(&mut self, other: Size<Dim>) {
+        self.w -= other.w;
+        self.h -= other.h;
+    }
+}
+
+impl<Dim> Sub<Size<Dim>> for Size<Dim> where Dim: SubAssign {
+    type Output = Size<Dim>;
+    fn sub(mut self, other: Size<Dim>) -> Self::Output {
+        self -= other;
+        self
+    }
+}
+
+impl<Dim> MulAssign<Dim> for Size<Dim> where Dim: MulAssign + Copy {
+    fn mul_assign(&mut self, factor: Dim) {
+        self.w *= factor;
+        self.h *= factor;
+    }
+}
+
+impl<Dim> Mul<Dim> for Size<Dim> where Dim: MulAssign + Copy {
+    type Output = Size<Dim>;
+    fn mul(mut self, factor: Dim) -> Self::Output {
+        self *= factor;
+        self
+    }
+}
+
+impl<Dim> DivAssign<Dim> for Size<Dim> where Dim: DivAssign + Copy {
+    fn div_assign(&mut self, divisor: Dim) {
+        self.w /= divisor;
+        self.h /= divisor;
+    }
+}
+
+impl<Dim> Div<Dim> for Size<Dim> where Dim: DivAssign + Copy {
+    type Output = Size<Dim>;
+    fn div(mut self, divisor: Dim) -> Self::Output {
+        self /= divisor;
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ops() {
+        assert_eq!(Size::new(1, 2) + Size::new(3, 4), Size::new(4, 6));
+        assert_eq!(Size::new(5, 7) - Size::new(3, 4), Size::new(2, 3));
+        assert_eq!(Size::new(2, 3) * 2, Size::new(4, 6));
+        assert_eq!(Size::new(5, 7) / 2, Size::new(2, 3));
+        assert_eq!(Size::new(5, 7).area(), 35);
+        assert!(Size::new(5, 0).is_collapsed());
+    }
+}
@@ -11,11 +11,41 @@ Color buffer is an abstraction intended to provide access to packed color data.
 There are many different color packing formats which determines how color data stored in frame buffers.
 Some of widely used is: RGB332, RGB444, RGB565, RGB888 and etc.
 
+The formats which packs several colors into single byte (like BW1, GS2 or IDX4) have bit order parameter.
+By default least significant bits comes first, but MSB-first order can be selected too (i.e. `format::BW1<MSB>`).
+The names of formats (like `format::BW1`) can still be used as values with default bit order.
+Images embedded using `#[embed("image.png", format = BW1, order = MSB)]` are packed in MSB-first order.
+
+When the format is known only at runtime the `DynFormat` can be used, which dispatches to static formats
+and exchanges colors as RGBA.
+
 ## Pixel views
 
 Pixel view allows access to color buffers as to two-dimensional pixel maps,
 which allows simplify and optimize usage of color buffers for rendering graphics.
 
+The mapping of pixels to colors in buffer is determined by pixel layout.
+Usually pixels stored row by row (`Linear` layout), but some displays uses vertical bytes
+which grouped in pages (`Paged` layout), so pixel views can wrap the display memory directly.
+
+Pixel views can be rotated or mirrored (see `Orientation`) to hide the physical orientation of display
+and can be cropped to sub-views which shares the same buffer.
+
+Static frame buffers are `ColorArray` which size is typenum integer. The `PixelArray` alias allows
+to write width and height as plain integers (i.e. `PixelArray<128, 64, format::BW1>`), but the size
+in bytes is still computed by typenum, so the bounds and compiler errors mentions typenum types.
+With `alloc` feature the heap-allocated `PixelBuffer` can be created with size and format known at runtime.
+
+## Allocation
+
+The heap-allocated buffers (`ColorVec`, `PixelBuffer`) uses `alloc` crate and available only with `alloc` feature.
+
+## Paints
+
+Paint sources determines colors of painted pixels. Besides solid colors there are linear, radial and conic
+gradients with optional dithering. Paints can fill rectangles of pixel views or be applied through masks
+like font glyphs.
+
 ## Examples
 
 ### Readonly image data and pixel view
@@ -30,15 +60,20 @@ pixel_view!(IMAGE<2, 2, format::RGB332>: (format::RGB332, &IMAGE_DATA));
 
 */
 
+#[cfg(any(feature = "alloc", test))]
+extern crate alloc;
+
 mod geom;
 mod color;
 mod pixel;
+mod paint;
 mod font;
 mod draw;
 
 pub use self::geom::*;
 pub use self::color::*;
 pub use self::pixel::*;
+pub use self::paint::*;
 pub use self::font::*;
 pub use self::draw::*;
 
@@ -0,0 +1,25 @@
+mod gradient;
+
+pub use self::gradient::*;
+
+use crate::{Point, RGBA};
+
+/// Paint source
+///
+/// Paint determines color of each pixel when filling rectangles, shapes or text.
+pub trait Paint {
+    /// Get color at point
+    fn color_at(&self, point: Point<i32>) -> RGBA;
+}
+
+impl Paint for RGBA {
+    fn color_at(&self, _point: Point<i32>) -> RGBA {
+        *self
+    }
+}
+
+impl<P: Paint + ?Sized> Paint for &P {
+    fn color_at(&self, point: Point<i32>) -> RGBA {
+        (**self).color_at(point)
+    }
+}
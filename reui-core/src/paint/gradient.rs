@@ -0,0 +1,260 @@
+use crate::{Point, RGBA, Bayer, quantize};
+use super::Paint;
+
+/// Color stop of gradient
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GradientStop {
+    /// Position of stop as fraction of 255
+    pub offset: u8,
+    /// Color at stop
+    pub color: RGBA,
+}
+
+impl GradientStop {
+    /// Create gradient stop
+    pub const fn new(offset: u8, color: RGBA) -> Self {
+        Self { offset, color }
+    }
+}
+
+/// Shape of gradient
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GradientShape {
+    /// Colors changes along line from start to end point
+    Linear { start: Point<i32>, end: Point<i32> },
+    /// Colors changes from center to circle with radius
+    Radial { center: Point<i32>, radius: u32 },
+    /// Colors changes clockwise around center beginning from angle (fraction of full turn)
+    Conic { center: Point<i32>, angle: u16 },
+}
+
+/// Gradient paint
+///
+/// Stops should be sorted by offset. Position of pixel is evaluated using integer math only.
+/// Optional ordered dithering can be used to avoid banding on formats with few bits per channel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gradient<'a> {
+    pub shape: GradientShape,
+    pub stops: &'a [GradientStop],
+    pub dither: u8,
+}
+
+impl<'a> Gradient<'a> {
+    /// Create gradient with shape and stops
+    pub const fn new(shape: GradientShape, stops: &'a [GradientStop]) -> Self {
+        Self { shape, stops, dither: 8 }
+    }
+
+    /// Create linear gradient
+    pub const fn linear(start: Point<i32>, end: Point<i32>, stops: &'a [GradientStop]) -> Self {
+        Self::new(GradientShape::Linear { start, end }, stops)
+    }
+
+    /// Create radial gradient
+    pub const fn radial(center: Point<i32>, radius: u32, stops: &'a [GradientStop]) -> Self {
+        Self::new(GradientShape::Radial { center, radius }, stops)
+    }
+
+    /// Create conic gradient
+    pub const fn conic(center: Point<i32>, angle: u16, stops: &'a [GradientStop]) -> Self {
+        Self::new(GradientShape::Conic { center, angle }, stops)
+    }
+
+    /// Enable dithering for target with specified number of bits per channel
+    ///
+    /// The 8 bits means no dithering.
+    pub const fn with_dither(mut self, bits: u8) -> Self {
+        self.dither = bits;
+        self
+    }
+
+    /// Get position of point in gradient as fraction of 65535
+    pub fn position(&self, point: Point<i32>) -> u16 {
+        match self.shape {
+            GradientShape::Linear { start, end } => {
+                let (dx, dy) = ((end.x - start.x) as i64, (end.y - start.y) as i64);
+                let (px, py) = ((point.x - start.x) as i64, (point.y - start.y) as i64);
+                let len = dx * dx + dy * dy;
+
+                if len == 0 {
+                    return 0;
+                }
+
+                ((px * dx + py * dy) * 65535 / len).clamp(0, 65535) as u16
+            }
+            GradientShape::Radial { center, radius } => {
+                let (dx, dy) = ((point.x - center.x) as i64, (point.y - center.y) as i64);
+
+                if radius == 0 {
+                    return 65535;
+                }
+
+                // distance with 8 fractional bits
+                let dist = isqrt(((dx * dx + dy * dy) as u64) << 16);
+
+                (dist * 65535 / (radius as u64 * 256)).min(65535) as u16
+            }
+            GradientShape::Conic { center, angle } => {
+                atan2(point.y - center.y, point.x - center.x).wrapping_sub(angle)
+            }
+        }
+    }
+
+    /// Get color at position as fraction of 65535
+    pub fn color(&self, position: u16) -> RGBA {
+        let stops = self.stops;
+        let position = position as u32;
+
+        let (first, last) = match (stops.first(), stops.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return RGBA::new(0, 0, 0, 0),
+        };
+
+        if position <= first.offset as u32 * 257 {
+            return first.color;
+        }
+
+        for pair in stops.windows(2) {
+            let (start, end) = (pair[0].offset as u32 * 257, pair[1].offset as u32 * 257);
+
+            if position <= end {
+                let t = (position - start) * 255 / (end - start).max(1);
+                return pair[0].color.mix(pair[1].color, t as u8);
+            }
+        }
+
+        last.color
+    }
+}
+
+impl Paint for Gradient<'_> {
+    fn color_at(&self, point: Point<i32>) -> RGBA {
+        let color = self.color(self.position(point));
+
+        if self.dither >= 8 {
+            return color;
+        }
+
+        let threshold = Bayer::Bayer4.threshold(point);
+        let dither = |value| quantize(value, self.dither, threshold);
+
+        RGBA::new(dither(color.r), dither(color.g), dither(color.b), color.a)
+    }
+}
+
+/// Integer square root
+fn isqrt(value: u64) -> u64 {
+    if value < 2 {
+        return value;
+    }
+
+    let mut x = 1u64 << ((64 - value.leading_zeros()).div_ceil(2));
+
+    loop {
+        let y = (x + value / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// Angle of vector clockwise from x axis as fraction of full turn
+fn atan2(y: i32, x: i32) -> u16 {
+    if x == 0 && y == 0 {
+        return 0;
+    }
+
+    let (ax, ay) = (x.unsigned_abs() as u64, y.unsigned_abs() as u64);
+    let (min, max) = if ax < ay { (ax, ay) } else { (ay, ax) };
+
+    // atan(z) ~ z * pi/4 + 0.273 * z * (1 - z) in range [0, 1]
+    let z = min * 65536 / max;
+    let a = ((z * 8192 + 2850 * z * (65536 - z) / 65536) >> 16) as u16;
+
+    let a = if ax < ay { 16384 - a } else { a };
+    let a = if x < 0 { 32768 - a } else { a };
+
+    if y < 0 { 0u16.wrapping_sub(a) } else { a }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const STOPS: [GradientStop; 3] = [
+        GradientStop::new(0, RGBA { r: 0, g: 0, b: 0, a: 255 }),
+        GradientStop::new(128, RGBA { r: 255, g: 0, b: 0, a: 255 }),
+        GradientStop::new(255, RGBA { r: 255, g: 255, b: 255, a: 0 }),
+    ];
+
+    #[test]
+    fn math() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        assert_eq!(isqrt(1 << 40), 1 << 20);
+        assert_eq!(isqrt(u64::MAX), 0xffff_ffff);
+
+        assert_eq!(atan2(0, 1), 0);
+        assert_eq!(atan2(1, 0), 16384);
+        assert_eq!(atan2(0, -1), 32768);
+        assert_eq!(atan2(-1, 0), 49152);
+        assert_eq!(atan2(1, 1), 8192);
+        assert!((atan2(1, 2) as i32 - 4836).abs() < 48);
+    }
+
+    #[test]
+    fn stops() {
+        let gradient = Gradient::linear(Point::new(0, 0), Point::new(10, 0), &STOPS);
+
+        assert_eq!(gradient.color(0), RGBA::new(0, 0, 0, 255));
+        assert_eq!(gradient.color(128 * 257), RGBA::new(255, 0, 0, 255));
+        assert_eq!(gradient.color(64 * 257), RGBA::new(127, 0, 0, 255));
+        assert_eq!(gradient.color(65535), RGBA::new(255, 255, 255, 0));
+        assert_eq!(Gradient::linear(Point::new(0, 0), Point::new(1, 0), &[]).color(5), RGBA::new(0, 0, 0, 0));
+    }
+
+    #[test]
+    fn shapes() {
+        let linear = Gradient::linear(Point::new(0, 0), Point::new(10, 10), &STOPS);
+
+        assert_eq!(linear.position(Point::new(-5, 0)), 0);
+        assert_eq!(linear.position(Point::new(10, 0)), 32767);
+        assert_eq!(linear.position(Point::new(0, 10)), 32767);
+        assert_eq!(linear.position(Point::new(20, 20)), 65535);
+
+        let radial = Gradient::radial(Point::new(5, 5), 10, &STOPS);
+
+        assert_eq!(radial.position(Point::new(5, 5)), 0);
+        assert_eq!(radial.position(Point::new(10, 5)), 32767);
+        assert_eq!(radial.position(Point::new(11, 13)), 65535);
+
+        let conic = Gradient::conic(Point::new(0, 0), 16384, &STOPS);
+
+        assert_eq!(conic.position(Point::new(0, 5)), 0);
+        assert_eq!(conic.position(Point::new(-5, 0)), 16384);
+        assert_eq!(conic.position(Point::new(5, 0)), 49152);
+    }
+
+    #[test]
+    fn dither() {
+        let stops = [
+            GradientStop::new(0, RGBA::new(100, 100, 100, 255)),
+            GradientStop::new(255, RGBA::new(100, 100, 100, 255)),
+        ];
+        let gradient = Gradient::linear(Point::new(0, 0), Point::new(10, 0), &stops);
+
+        assert_eq!(gradient.color_at(Point::new(3, 2)), RGBA::new(100, 100, 100, 255));
+
+        let gradient = gradient.with_dither(5);
+
+        assert_eq!(gradient.color_at(Point::new(0, 0)), RGBA::new(98, 98, 98, 255));
+        assert_eq!(gradient.color_at(Point::new(0, 3)), RGBA::new(106, 106, 106, 255));
+
+        let sum: u32 = (0..4).flat_map(|y| (0..4).map(move |x| Point::new(x, y)))
+            .map(|point| gradient.color_at(point).r as u32).sum();
+
+        assert_eq!(sum, 1592);
+    }
+}
@@ -1,25 +1,88 @@
-use typenum::{Prod};
-use crate::{Point, Size, ColorFmt, ColorBuf, ColorBufMut, ColorArray};
+mod layout;
+mod orient;
+mod blit;
+mod double;
+mod damage;
+#[cfg(any(feature = "alloc", test))]
+mod buffer;
 
-pub type PixelArray<W, H, Fmt> = ColorArray<Prod<W, H>, Fmt>;
+pub use self::layout::*;
+pub use self::orient::*;
+pub use self::blit::*;
+pub use self::double::*;
+pub use self::damage::*;
+#[cfg(any(feature = "alloc", test))]
+pub use self::buffer::*;
+
+use core::ops::Range;
+use typenum::{Prod, U};
+use crate::{Point, Size, Rect, ColorFmt, ColorBuf, ColorBufMut, ColorArray, Paint, Blend, BlendOp, GS, RGBA};
+
+/// Static-sized buffer for pixels with specified width and height
+///
+/// This is only an alias which converts the width and height to typenum for [`ColorArray`].
+/// The storage is not sized by const generics, because stable Rust cannot compute the byte length
+/// from them yet, so typenum types still appears in bounds and errors.
+pub type PixelArray<const W: usize, const H: usize, Fmt> = ColorArray<Prod<U<W>, U<H>>, Fmt>;
 
 #[derive(Debug, Clone)]
-pub struct PixelView<Buf> {
+pub struct PixelView<Buf, Layout = Linear> {
+    /// Left top point of view in buffer
+    origin: Point<usize>,
+    /// Physical size of view
     size: Size<usize>,
+    /// Distance between rows in pixels
+    stride: usize,
+    layout: Layout,
+    orient: Orientation,
     data: Buf,
 }
 
 impl<Buf> PixelView<Buf> {
     pub const fn new(size: Size<usize>, data: Buf) -> Self {
-        Self { size, data }
+        Self::new_with_layout(size, Linear, data)
+    }
+
+    pub const fn new_with_stride(size: Size<usize>, stride: usize, data: Buf) -> Self {
+        Self { origin: Point::new(0, 0), size, stride, layout: Linear, orient: Orientation::Rotate0, data }
     }
 
     pub fn wrap(data: Buf) -> Self {
         Self::new(Size::default(), data)
     }
+}
+
+impl<Buf, Layout> PixelView<Buf, Layout> {
+    pub const fn new_with_layout(size: Size<usize>, layout: Layout, data: Buf) -> Self {
+        Self { origin: Point::new(0, 0), size, stride: size.w, layout, orient: Orientation::Rotate0, data }
+    }
 
+    /// Set size of view
+    ///
+    /// The size is logical, so it will be swapped for rotated views.
+    /// The stride is kept unless it is less than physical width, so it can be set before or after.
     pub fn with_size(mut self, size: Size<usize>) -> Self {
-        self.size = size;
+        self.size = self.orient.map_size(size);
+        self.stride = self.stride.max(self.size.w);
+        self
+    }
+
+    /// Set stride of view in pixels
+    pub fn with_stride(mut self, stride: usize) -> Self {
+        self.stride = stride;
+        self
+    }
+
+    pub fn with_layout<ToLayout>(self, layout: ToLayout) -> PixelView<Buf, ToLayout> {
+        let PixelView { origin, size, stride, orient, data, .. } = self;
+        PixelView { origin, size, stride, layout, orient, data }
+    }
+
+    /// Set orientation of view
+    ///
+    /// The physical size of view is kept, so logical size will be swapped for rotated views.
+    pub fn with_orientation(mut self, orient: Orientation) -> Self {
+        self.orient = orient;
         self
     }
 
@@ -27,33 +90,225 @@ impl<Buf> PixelView<Buf> {
         self.data
     }
 
+    /// Get logical size of view
     pub fn size(&self) -> Size<usize> {
-        self.size
+        self.orient.map_size(self.size)
+    }
+
+    /// Get stride of view in pixels
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    pub fn layout(&self) -> &Layout {
+        &self.layout
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        self.orient
+    }
+
+    /// Get left top point of view in buffer
+    pub fn origin(&self) -> Point<usize> {
+        self.origin
+    }
+
+    fn sub_rect(&self, rect: Rect<usize>) -> (Point<usize>, Size<usize>) {
+        let size = self.size();
+        let l = rect.left().min(size.w);
+        let t = rect.top().min(size.h);
+        let r = rect.right().min(size.w);
+        let b = rect.bottom().min(size.h);
+
+        let rect = self.orient.map_rect((l, t, r, b).into(), self.size);
+
+        (Point::new(self.origin.x + rect.point.x, self.origin.y + rect.point.y), rect.size)
+    }
+
+    /// Get readonly view of the part of view
+    ///
+    /// The specified rectangle is clipped by view bounds.
+    pub fn sub_view(&self, rect: Rect<usize>) -> PixelView<&Buf, &Layout> {
+        let (origin, size) = self.sub_rect(rect);
+        PixelView {
+            origin, size, stride: self.stride, layout: &self.layout, orient: self.orient, data: &self.data,
+        }
+    }
+
+    /// Get mutable view of the part of view
+    ///
+    /// The specified rectangle is clipped by view bounds.
+    pub fn sub_view_mut(&mut self, rect: Rect<usize>) -> PixelView<&mut Buf, &Layout> {
+        let (origin, size) = self.sub_rect(rect);
+        PixelView {
+            origin, size, stride: self.stride, layout: &self.layout, orient: self.orient, data: &mut self.data,
+        }
     }
 }
 
-impl<Buf> PixelView<Buf>
+impl<Buf, Layout> PixelView<Buf, Layout>
 where
     Buf: ColorBuf,
+    Layout: PixelLayout,
 {
+    /// Set stride of view in bytes
+    ///
+    /// Panics when stride in bytes doesn't hold whole number of pixels, like 4-byte aligned rows
+    /// of 24-bit colors.
+    pub fn with_byte_stride(self, bytes: usize) -> Self {
+        let bits = self.data.color_bits();
+        let stride = self.layout.stride_from_bytes(bytes, bits);
+
+        self.with_stride(stride)
+    }
+
+    fn buffer_point(&self, point: Point<usize>) -> Point<usize> {
+        let point = self.orient.map_point(point, self.size);
+        Point::new(self.origin.x + point.x, self.origin.y + point.y)
+    }
+
+    fn color_index(&self, point: Point<usize>) -> usize {
+        let bits = self.data.color_bits();
+
+        self.layout.color_index(self.buffer_point(point), self.stride, bits)
+    }
+
+    /// Get range of colors in buffer for the part of row when it stored contiguously
+    fn row_span(&self, point: Point<usize>, length: usize) -> Option<Range<usize>> {
+        if !self.orient.keeps_rows() {
+            return None;
+        }
+
+        let bits = self.data.color_bits();
+
+        self.layout.color_span(self.buffer_point(point), length, self.stride, bits)
+    }
+
     pub fn get(&self, point: Point<usize>) -> <Buf::ColorFmt as ColorFmt>::ColorType {
-        let index = point.x + point.y * self.size.w;
+        let index = self.color_index(point);
 
         self.data.get(index)
     }
 
+    /// Get color of pixel
+    ///
+    /// Returns `None` when point is outside of view.
+    pub fn get_checked(&self, point: Point<usize>) -> Option<<Buf::ColorFmt as ColorFmt>::ColorType> {
+        let size = self.size();
+
+        if point.x < size.w && point.y < size.h {
+            Some(self.get(point))
+        } else {
+            None
+        }
+    }
+
     pub fn set(&mut self, point: Point<usize>, color: <Buf::ColorFmt as ColorFmt>::ColorType)
     where
         Buf: ColorBufMut,
     {
-        let index = point.x + point.y * self.size.w;
+        let index = self.color_index(point);
 
         self.data.set(index, color)
     }
+
+    /// Fill rectangle by single color
+    ///
+    /// The specified rectangle is clipped by view bounds.
+    pub fn fill_rect(&mut self, rect: Rect<usize>, color: <Buf::ColorFmt as ColorFmt>::ColorType)
+    where
+        Buf: ColorBufMut,
+        <Buf::ColorFmt as ColorFmt>::ColorType: Clone,
+    {
+        let bits = self.data.color_bits();
+        let (origin, size) = self.sub_rect(rect);
+
+        for y in origin.y..origin.y + size.h {
+            let point = Point::new(origin.x, y);
+
+            if let Some(span) = self.layout.color_span(point, size.w, self.stride, bits) {
+                self.data.fill(span, color.clone());
+            } else {
+                for x in origin.x..origin.x + size.w {
+                    let index = self.layout.color_index(Point::new(x, y), self.stride, bits);
+                    self.data.set(index, color.clone());
+                }
+            }
+        }
+    }
+
+    /// Fill whole view by single color
+    pub fn fill(&mut self, color: <Buf::ColorFmt as ColorFmt>::ColorType)
+    where
+        Buf: ColorBufMut,
+        <Buf::ColorFmt as ColorFmt>::ColorType: Clone,
+    {
+        self.fill_rect(Rect::new(Point::new(0, 0), self.size()), color)
+    }
+
+    /// Paint rectangle of view using paint source
+    ///
+    /// The colors of paint blends over existing pixels.
+    pub fn paint_rect<P: Paint>(&mut self, rect: Rect<usize>, paint: &P)
+    where
+        Buf: ColorBufMut,
+        <Buf::ColorFmt as ColorFmt>::ColorType: Blend<RGBA>,
+    {
+        let size = self.size();
+        let (l, t) = (rect.left().min(size.w), rect.top().min(size.h));
+        let (r, b) = (rect.right().min(size.w), rect.bottom().min(size.h));
+
+        for y in t..b {
+            for x in l..r {
+                let point = Point::new(x, y);
+                let color = paint.color_at(Point::new(x as i32, y as i32));
+
+                self.set(point, self.get(point).blend(color, BlendOp::SrcOver, 255));
+            }
+        }
+    }
+
+    /// Paint through mask using paint source
+    ///
+    /// The gray level of mask pixels is used as coverage, so glyphs of fonts or rasterized shapes
+    /// can be painted using solid colors or gradients. The paint is evaluated in coordinates of this view.
+    pub fn paint_mask<MaskBuf, MaskLayout, P>(&mut self, point: Point<usize>, mask: &PixelView<MaskBuf, MaskLayout>, paint: &P)
+    where
+        Buf: ColorBufMut,
+        <Buf::ColorFmt as ColorFmt>::ColorType: Blend<RGBA>,
+        MaskBuf: ColorBuf,
+        MaskLayout: PixelLayout,
+        <MaskBuf::ColorFmt as ColorFmt>::ColorType: Into<GS>,
+        P: Paint,
+    {
+        let size = self.size();
+        let mask_size = mask.size();
+        let w = mask_size.w.min(size.w.saturating_sub(point.x));
+        let h = mask_size.h.min(size.h.saturating_sub(point.y));
+
+        for y in 0..h {
+            for x in 0..w {
+                let coverage = mask.get(Point::new(x, y)).into().v;
+
+                if coverage == 0 {
+                    continue;
+                }
+
+                let target = Point::new(point.x + x, point.y + y);
+                let color = paint.color_at(Point::new(target.x as i32, target.y as i32));
+
+                self.set(target, self.get(target).blend(color, BlendOp::SrcOver, coverage));
+            }
+        }
+    }
 }
 
 #[macro_export]
 macro_rules! pixel_view {
+    ($name: ident < $width: tt, $height: tt, $stride: tt, $fmt: path > : $($data:tt)+ ) => {
+        pub static $name: $crate::PixelView<(&$fmt, &[u8])> =
+            $crate::PixelView::new_with_stride($crate::Size::new($width, $height), $stride, $($data)+);
+    };
     ($name: ident < $width: tt, $height: tt, $fmt: path > : $($data:tt)+ ) => {
         pub static $name: $crate::PixelView<(&$fmt, &[u8])> =
             $crate::PixelView::new($crate::Size::new($width, $height), $($data)+);
@@ -62,7 +317,6 @@ macro_rules! pixel_view {
 
 #[cfg(test)]
 mod test {
-    use typenum::*;
     use crate::*;
 
     #[test]
@@ -77,18 +331,276 @@ mod test {
         assert_eq!(IMAGE.get(Point::new(1, 1)), RGB::new(36, 108, 0));
     }
 
+    #[test]
+    fn test_image_stride() {
+        static IMAGE_DATA: [u8; 6] = [1, 97, 0, 186, 44, 0];
+
+        pixel_view!(IMAGE<2, 2, 3, format::RGB332>: (&format::RGB332::DEFAULT, &IMAGE_DATA));
+
+        assert_eq!(IMAGE.stride(), 3);
+        assert_eq!(IMAGE.get(Point::new(0, 0)), RGB::new(0, 0, 85));
+        assert_eq!(IMAGE.get(Point::new(1, 0)), RGB::new(108, 0, 85));
+        assert_eq!(IMAGE.get(Point::new(0, 1)), RGB::new(180, 216, 170));
+        assert_eq!(IMAGE.get(Point::new(1, 1)), RGB::new(36, 108, 0));
+    }
+
+    #[test]
+    fn test_byte_stride() {
+        let mut view = PixelView::new(Size::new(10, 2), (<format::BW1>::DEFAULT, [0u8; 4]))
+            .with_byte_stride(2);
+
+        assert_eq!(view.stride(), 16);
+
+        view.set(Point::new(0, 1), true.into());
+        view.set(Point::new(9, 1), true.into());
+
+        assert_eq!(view.get(Point::new(0, 1)), true.into());
+        assert_eq!(view.get(Point::new(9, 1)), true.into());
+        assert_eq!(view.unwrap().1, [0, 0, 0b0000_0001, 0b0000_0010]);
+    }
+
+    #[test]
+    fn test_byte_stride_rgb888() {
+        let mut view = PixelView::new(Size::new(2, 2), (format::RGB888, [0u8; 2 * 12]))
+            .with_byte_stride(12);
+
+        assert_eq!(view.stride(), 4);
+
+        view.set(Point::new(1, 1), RGB::new(1, 2, 3));
+
+        assert_eq!(view.unwrap().1[15..18], [1, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_byte_stride_partial() {
+        let _ = PixelView::new(Size::new(1, 2), (format::RGB888, [0u8; 2 * 4]))
+            .with_byte_stride(4);
+    }
+
+    #[test]
+    fn test_size_stride() {
+        let view = PixelView::wrap((format::GS8, [0u8; 24])).with_size(Size::new(4, 3));
+
+        assert_eq!(view.stride(), 4);
+
+        let view = view.with_stride(8).with_size(Size::new(5, 3));
+
+        assert_eq!(view.stride(), 8);
+
+        let view = view.with_size(Size::new(10, 2));
+
+        assert_eq!(view.stride(), 10);
+
+        let view = PixelView::wrap((format::GS8, [0u8; 24])).with_orientation(Orientation::Rotate90)
+            .with_size(Size::new(2, 6));
+
+        assert_eq!(view.stride(), 6);
+    }
+
+    #[test]
+    fn test_sub_view() {
+        let mut view = PixelView::new(Size::new(4, 4), (format::RGB888, [0u8; 4 * 4 * 3]));
+
+        {
+            let mut sub = view.sub_view_mut(Rect::new(Point::new(1, 1), Size::new(2, 2)));
+
+            assert_eq!(sub.size(), Size::new(2, 2));
+            assert_eq!(sub.origin(), Point::new(1, 1));
+
+            sub.set(Point::new(0, 0), RGB::new(1, 2, 3));
+            sub.set(Point::new(1, 1), RGB::new(4, 5, 6));
+
+            let mut sub = sub.sub_view_mut(Rect::new(Point::new(1, 0), Size::new(4, 4)));
+
+            assert_eq!(sub.size(), Size::new(1, 2));
+            assert_eq!(sub.origin(), Point::new(2, 1));
+
+            sub.set(Point::new(0, 0), RGB::new(7, 8, 9));
+        }
+
+        assert_eq!(view.get(Point::new(1, 1)), RGB::new(1, 2, 3));
+        assert_eq!(view.get(Point::new(2, 2)), RGB::new(4, 5, 6));
+        assert_eq!(view.get(Point::new(2, 1)), RGB::new(7, 8, 9));
+        assert_eq!(view.get(Point::new(0, 0)), RGB::new(0, 0, 0));
+
+        let sub = view.sub_view(Rect::new(Point::new(2, 2), Size::new(4, 4)));
+
+        assert_eq!(sub.size(), Size::new(2, 2));
+        assert_eq!(sub.get(Point::new(0, 0)), RGB::new(4, 5, 6));
+
+        let sub = view.sub_view(Rect::new(Point::new(5, 1), Size::new(2, 2)));
+
+        assert_eq!(sub.size(), Size::new(0, 2));
+    }
+
+    #[test]
+    fn test_sub_view_stride() {
+        let mut view = PixelView::new(Size::new(10, 4), (<format::GS4>::DEFAULT, [0u8; 6 * 4]))
+            .with_stride(12);
+
+        view.sub_view_mut(Rect::new(Point::new(9, 2), Size::new(3, 2)))
+            .set(Point::new(0, 1), 255.into());
+
+        assert_eq!(view.get(Point::new(9, 3)), 255.into());
+        assert_eq!(view.unwrap().1[(3 * 12 + 9) / 2], 0b1111_0000);
+    }
+
+    #[test]
+    fn test_sub_view_bounds() {
+        let mut view = PixelView::new(Size::new(4, 3), (format::GS8, [0u8; 4 * 3]))
+            .with_orientation(Orientation::Rotate90);
+
+        {
+            let mut sub = view.sub_view_mut(Rect::new(Point::new(1, 1), Size::new(1, 5)));
+
+            assert_eq!(sub.size(), Size::new(1, 3));
+
+            sub.set(Point::new(0, 0), 1.into());
+
+            assert_eq!(sub.get_checked(Point::new(0, 0)), Some(1.into()));
+            assert_eq!(sub.get_checked(Point::new(1, 0)), None);
+            assert_eq!(sub.get_checked(Point::new(0, 3)), None);
+        }
+
+        assert_eq!(view.get(Point::new(1, 1)), 1.into());
+        assert_eq!(view.get_checked(Point::new(3, 0)), None);
+        assert_eq!(view.unwrap().1.iter().filter(|v| **v != 0).count(), 1);
+    }
+
+    #[test]
+    fn test_orientation() {
+        let mut view = PixelView::new(Size::new(3, 2), (format::RGB888, [0u8; 3 * 2 * 3]));
+
+        view.set(Point::new(0, 0), RGB::new(1, 1, 1));
+        view.set(Point::new(2, 0), RGB::new(2, 2, 2));
+        view.set(Point::new(0, 1), RGB::new(3, 3, 3));
+
+        let view = view.with_orientation(Orientation::Rotate90);
+
+        assert_eq!(view.size(), Size::new(2, 3));
+        assert_eq!(view.get(Point::new(0, 0)), RGB::new(2, 2, 2));
+        assert_eq!(view.get(Point::new(0, 2)), RGB::new(1, 1, 1));
+        assert_eq!(view.get(Point::new(1, 2)), RGB::new(3, 3, 3));
+
+        let view = view.with_orientation(Orientation::Rotate180);
+
+        assert_eq!(view.size(), Size::new(3, 2));
+        assert_eq!(view.get(Point::new(2, 1)), RGB::new(1, 1, 1));
+        assert_eq!(view.get(Point::new(0, 1)), RGB::new(2, 2, 2));
+
+        let view = view.with_orientation(Orientation::Rotate270);
+
+        assert_eq!(view.size(), Size::new(2, 3));
+        assert_eq!(view.get(Point::new(0, 0)), RGB::new(3, 3, 3));
+        assert_eq!(view.get(Point::new(1, 0)), RGB::new(1, 1, 1));
+        assert_eq!(view.get(Point::new(1, 2)), RGB::new(2, 2, 2));
+
+        let view = view.with_orientation(Orientation::FlipH);
+
+        assert_eq!(view.get(Point::new(2, 0)), RGB::new(1, 1, 1));
+        assert_eq!(view.get(Point::new(2, 1)), RGB::new(3, 3, 3));
+
+        let view = view.with_orientation(Orientation::FlipV);
+
+        assert_eq!(view.get(Point::new(0, 1)), RGB::new(1, 1, 1));
+        assert_eq!(view.get(Point::new(0, 0)), RGB::new(3, 3, 3));
+    }
+
+    #[test]
+    fn test_orientation_sub_view() {
+        let mut view = PixelView::new(Size::new(4, 2), (<format::GS8>::DEFAULT, [0u8; 4 * 2]))
+            .with_orientation(Orientation::Rotate90);
+
+        {
+            let mut sub = view.sub_view_mut(Rect::new(Point::new(1, 1), Size::new(4, 2)));
+
+            assert_eq!(sub.size(), Size::new(1, 2));
+
+            sub.set(Point::new(0, 0), 1.into());
+            sub.set(Point::new(0, 1), 2.into());
+        }
+
+        assert_eq!(view.get(Point::new(1, 1)), 1.into());
+        assert_eq!(view.get(Point::new(1, 2)), 2.into());
+        assert_eq!(view.unwrap().1, [0, 0, 0, 0, 0, 2, 1, 0]);
+    }
+
+    #[test]
+    fn test_fill_rect() {
+        let mut view = PixelView::new(Size::new(10, 3), (<format::BW1>::DEFAULT, [0u8; 6]))
+            .with_byte_stride(2);
+
+        view.fill_rect(Rect::new(Point::new(2, 1), Size::new(10, 5)), true.into());
+
+        assert_eq!(view.unwrap().1, [0, 0, 0b1111_1100, 0b0000_0011, 0b1111_1100, 0b0000_0011]);
+
+        let mut view = PixelView::new(Size::new(4, 2), (<format::GS8>::DEFAULT, [0u8; 8]))
+            .with_orientation(Orientation::Rotate90);
+
+        view.fill_rect(Rect::new(Point::new(1, 1), Size::new(1, 2)), 5.into());
+
+        assert_eq!(view.unwrap().1, [0, 0, 0, 0, 0, 5, 5, 0]);
+
+        let mut view = PixelView::new_with_layout(Size::new(4, 16), Paged, (<format::BW1>::DEFAULT, [0u8; 8]));
+
+        view.fill_rect(Rect::new(Point::new(1, 6), Size::new(2, 4)), true.into());
+
+        assert_eq!(view.unwrap().1, [0, 0b1100_0000, 0b1100_0000, 0, 0, 0b0000_0011, 0b0000_0011, 0]);
+    }
+
+    #[test]
+    fn test_fill() {
+        let mut data = PixelArray::<4, 3, format::RGB565>::new();
+        let mut view = PixelView::new(Size::new(4, 3), &mut data);
+
+        view.fill(RGB::new(0, 252, 0));
+
+        let data: &[u8] = data.as_ref();
+
+        assert!(data.chunks(2).all(|bytes| bytes == [0b0000_0111, 0b1110_0000]));
+    }
+
+    #[test]
+    fn test_paint_rect() {
+        let stops = [
+            GradientStop::new(0, RGBA::new(0, 0, 0, 255)),
+            GradientStop::new(255, RGBA::new(255, 255, 255, 255)),
+        ];
+        let gradient = Gradient::linear(Point::new(0, 0), Point::new(3, 0), &stops);
+        let mut view = PixelView::new(Size::new(4, 2), (format::GS8, [7u8; 8]));
+
+        view.paint_rect(Rect::new(Point::new(0, 1), Size::new(8, 1)), &gradient);
+
+        assert_eq!(view.unwrap().1, [7, 7, 7, 7, 0, 85, 170, 255]);
+    }
+
+    #[test]
+    fn test_paint_mask() {
+        let mask = PixelView::new(Size::new(2, 2), (format::GS8, [255, 0, 128, 255]));
+        let mut view = PixelView::new(Size::new(3, 2), (format::RGB888, [0u8; 18]));
+
+        view.paint_mask(Point::new(1, 0), &mask, &RGBA::new(200, 100, 0, 255));
+
+        assert_eq!(view.get(Point::new(0, 0)), RGB::new(0, 0, 0));
+        assert_eq!(view.get(Point::new(1, 0)), RGB::new(200, 100, 0));
+        assert_eq!(view.get(Point::new(2, 0)), RGB::new(0, 0, 0));
+        assert_eq!(view.get(Point::new(1, 1)), RGB::new(100, 50, 0));
+        assert_eq!(view.get(Point::new(2, 1)), RGB::new(200, 100, 0));
+    }
+
     #[test]
     fn test_rgb888() {
         type Format = format::RGB888;
-        type Width = U128;
-        type Height = U162;
+        const WIDTH: usize = 128;
+        const HEIGHT: usize = 162;
 
-        let mut data = PixelArray::<Width, Height, Format>::new();
-        let mut view = PixelView::new(Size::new(Width::USIZE, Height::USIZE), &mut data);
+        let mut data = PixelArray::<WIDTH, HEIGHT, Format>::new();
+        let mut view = PixelView::new(Size::new(WIDTH, HEIGHT), &mut data);
 
         let size = view.size();
 
-        assert_eq!(size, (Width::USIZE, Height::USIZE).into());
+        assert_eq!(size, (WIDTH, HEIGHT).into());
 
         let colors = [
             RGB::new(0, 0, 248),
@@ -120,15 +632,15 @@ mod test {
     #[test]
     fn test_rgb565() {
         type Format = format::RGB565;
-        type Width = U128;
-        type Height = U162;
+        const WIDTH: usize = 128;
+        const HEIGHT: usize = 162;
 
-        let mut data = PixelArray::<Width, Height, Format>::new();
-        let mut view = PixelView::new(Size::new(Width::USIZE, Height::USIZE), &mut data);
+        let mut data = PixelArray::<WIDTH, HEIGHT, Format>::new();
+        let mut view = PixelView::new(Size::new(WIDTH, HEIGHT), &mut data);
 
         let size = view.size();
 
-        assert_eq!(size, (Width::USIZE, Height::USIZE).into());
+        assert_eq!(size, (WIDTH, HEIGHT).into());
 
         let colors = [
             RGB::new(0, 0, 248),
@@ -156,4 +668,25 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_paged_bw1() {
+        let mut view = PixelView::new_with_layout(Size::new(16, 16), Paged, (<format::BW1>::DEFAULT, [0u8; 32]));
+
+        view.set(Point::new(0, 0), true.into());
+        view.set(Point::new(3, 10), true.into());
+        view.set(Point::new(15, 15), true.into());
+
+        assert_eq!(view.get(Point::new(0, 0)), true.into());
+        assert_eq!(view.get(Point::new(0, 1)), false.into());
+        assert_eq!(view.get(Point::new(3, 10)), true.into());
+        assert_eq!(view.get(Point::new(15, 15)), true.into());
+
+        let (_, data) = view.unwrap();
+
+        assert_eq!(data[0], 0b0000_0001);
+        assert_eq!(data[16 + 3], 0b0000_0100);
+        assert_eq!(data[16 + 15], 0b1000_0000);
+        assert_eq!(data.iter().filter(|byte| **byte != 0).count(), 3);
+    }
 }
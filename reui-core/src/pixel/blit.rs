@@ -0,0 +1,322 @@
+use crate::{ConstDefault, Point, Size, Rect, ColorFmt, ColorAlpha, ColorBuf, ColorBufMut, ColorDepth, Blend, BlendOp, Bayer, RGBA};
+use super::{PixelView, PixelLayout};
+
+/// Blitting mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlitMode<Color> {
+    /// Copy all pixels
+    Copy,
+    /// Copy pixels which alpha is not less than threshold
+    Alpha(u8),
+    /// Copy pixels which color is not equal to key color
+    ColorKey(Color),
+}
+
+impl<Color> ConstDefault for BlitMode<Color> {
+    const DEFAULT: Self = BlitMode::Copy;
+}
+
+impl<Color> Default for BlitMode<Color> {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl<Color> BlitMode<Color> {
+    /// Check that pixel with specified color should be copied
+    pub fn pass(&self, color: &Color) -> bool
+    where
+        Color: ColorAlpha + PartialEq,
+    {
+        match self {
+            BlitMode::Copy => true,
+            BlitMode::Alpha(threshold) => color.alpha() >= *threshold,
+            BlitMode::ColorKey(key) => color != key,
+        }
+    }
+}
+
+/// Clip source rectangle and destination point by views sizes
+fn clip(src_size: Size<usize>, src_rect: Rect<usize>, dst_size: Size<usize>, dst_point: Point<usize>) -> (Point<usize>, Size<usize>) {
+    let l = src_rect.left().min(src_size.w);
+    let t = src_rect.top().min(src_size.h);
+    let r = src_rect.right().min(src_size.w);
+    let b = src_rect.bottom().min(src_size.h);
+
+    let w = (r - l).min(dst_size.w.saturating_sub(dst_point.x));
+    let h = (b - t).min(dst_size.h.saturating_sub(dst_point.y));
+
+    (Point::new(l, t), Size::new(w, h))
+}
+
+/// Copy pixels from one view to another with color conversion
+///
+/// The source rectangle is clipped by both views. Rows which is stored contiguously
+/// in both views are converted by spans. Use [`copy`] when views has same format.
+pub fn blit<SrcBuf, SrcLayout, DstBuf, DstLayout>(
+    src: &PixelView<SrcBuf, SrcLayout>,
+    src_rect: Rect<usize>,
+    dst: &mut PixelView<DstBuf, DstLayout>,
+    dst_point: Point<usize>,
+    mode: BlitMode<<SrcBuf::ColorFmt as ColorFmt>::ColorType>,
+) where
+    SrcBuf: ColorBuf,
+    SrcLayout: PixelLayout,
+    DstBuf: ColorBufMut,
+    DstLayout: PixelLayout,
+    <SrcBuf::ColorFmt as ColorFmt>::ColorType: Into<<DstBuf::ColorFmt as ColorFmt>::ColorType> + ColorAlpha + PartialEq,
+{
+    let (src_point, size) = clip(src.size(), src_rect, dst.size(), dst_point);
+
+    for y in 0..size.h {
+        let src_row = Point::new(src_point.x, src_point.y + y);
+        let dst_row = Point::new(dst_point.x, dst_point.y + y);
+
+        match (src.row_span(src_row, size.w), dst.row_span(dst_row, size.w)) {
+            (Some(src_span), Some(dst_span)) => {
+                let colors = src.data.gets(src_span, size.w, size.w);
+
+                if let BlitMode::Copy = mode {
+                    dst.data.sets(dst_span, size.w, size.w, colors.map(Into::into));
+                } else {
+                    for (index, color) in dst_span.zip(colors) {
+                        if mode.pass(&color) {
+                            dst.data.set(index, color.into());
+                        }
+                    }
+                }
+            }
+            _ => {
+                for x in 0..size.w {
+                    let color = src.get(Point::new(src_row.x + x, src_row.y));
+
+                    if mode.pass(&color) {
+                        dst.set(Point::new(dst_row.x + x, dst_row.y), color.into());
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Blend pixels from one view over another
+///
+/// Each source pixel blends over corresponding destination pixel using operator and global opacity.
+pub fn blit_blend<SrcBuf, SrcLayout, DstBuf, DstLayout>(
+    src: &PixelView<SrcBuf, SrcLayout>,
+    src_rect: Rect<usize>,
+    dst: &mut PixelView<DstBuf, DstLayout>,
+    dst_point: Point<usize>,
+    op: BlendOp,
+    opacity: u8,
+) where
+    SrcBuf: ColorBuf,
+    SrcLayout: PixelLayout,
+    DstBuf: ColorBufMut,
+    DstLayout: PixelLayout,
+    <DstBuf::ColorFmt as ColorFmt>::ColorType: Blend<<SrcBuf::ColorFmt as ColorFmt>::ColorType>,
+{
+    let (src_point, size) = clip(src.size(), src_rect, dst.size(), dst_point);
+
+    if opacity == 0 {
+        return;
+    }
+
+    for y in 0..size.h {
+        for x in 0..size.w {
+            let color = src.get(Point::new(src_point.x + x, src_point.y + y));
+            let point = Point::new(dst_point.x + x, dst_point.y + y);
+
+            dst.set(point, dst.get(point).blend(color, op, opacity));
+        }
+    }
+}
+
+/// Copy pixels from one view to another reducing color depth with ordered dithering
+///
+/// The dithering pattern is aligned to destination view, so adjacent blits joins seamlessly.
+pub fn blit_dithered<SrcBuf, SrcLayout, DstBuf, DstLayout>(
+    src: &PixelView<SrcBuf, SrcLayout>,
+    src_rect: Rect<usize>,
+    dst: &mut PixelView<DstBuf, DstLayout>,
+    dst_point: Point<usize>,
+    matrix: Bayer,
+) where
+    SrcBuf: ColorBuf,
+    SrcLayout: PixelLayout,
+    DstBuf: ColorBufMut,
+    DstBuf::ColorFmt: ColorDepth,
+    DstLayout: PixelLayout,
+    <SrcBuf::ColorFmt as ColorFmt>::ColorType: Into<RGBA>,
+{
+    let (src_point, size) = clip(src.size(), src_rect, dst.size(), dst_point);
+
+    for y in 0..size.h {
+        for x in 0..size.w {
+            let color = src.get(Point::new(src_point.x + x, src_point.y + y)).into();
+            let point = Point::new(dst_point.x + x, dst_point.y + y);
+            let threshold = matrix.threshold(Point::new(point.x as i32, point.y as i32));
+
+            dst.set(point, DstBuf::ColorFmt::reduce(color, threshold));
+        }
+    }
+}
+
+/// Copy pixels from one view to another with same format
+///
+/// The packed color data copies as is, so whole bytes of rows copies directly when it possible.
+pub fn copy<SrcBuf, SrcLayout, DstBuf, DstLayout>(
+    src: &PixelView<SrcBuf, SrcLayout>,
+    src_rect: Rect<usize>,
+    dst: &mut PixelView<DstBuf, DstLayout>,
+    dst_point: Point<usize>,
+) where
+    SrcBuf: ColorBuf,
+    SrcLayout: PixelLayout,
+    DstBuf: ColorBufMut<ColorFmt = SrcBuf::ColorFmt>,
+    DstLayout: PixelLayout,
+{
+    let bits = src.data.color_bits();
+    let (src_point, size) = clip(src.size(), src_rect, dst.size(), dst_point);
+
+    for y in 0..size.h {
+        let src_row = Point::new(src_point.x, src_point.y + y);
+        let dst_row = Point::new(dst_point.x, dst_point.y + y);
+
+        match (src.row_span(src_row, size.w), dst.row_span(dst_row, size.w)) {
+            (Some(src_span), Some(dst_span)) if bits.is_multiple_of(8) => {
+                let n = bits / 8;
+
+                dst.data.bytes_mut()[dst_span.start * n..dst_span.end * n]
+                    .copy_from_slice(&src.data.bytes()[src_span.start * n..src_span.end * n]);
+            }
+            (Some(src_span), Some(dst_span)) => {
+                dst.data.sets(dst_span, size.w, size.w, src.data.gets(src_span, size.w, size.w));
+            }
+            _ => {
+                for x in 0..size.w {
+                    let color = src.get(Point::new(src_row.x + x, src_row.y));
+                    dst.set(Point::new(dst_row.x + x, dst_row.y), color);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{format, Orientation, Paged, GS, RGB};
+
+    #[test]
+    fn blit_convert() {
+        let src = PixelView::new(Size::new(2, 2), (format::RGBA8888, [
+            255, 0, 0, 255,  0, 255, 0, 100,
+            0, 0, 255, 255,  255, 255, 255, 0,
+        ]));
+        let mut dst = PixelView::new(Size::new(3, 3), (format::RGB565, [0u8; 18]));
+
+        blit(&src, Rect::new(Point::new(0, 0), Size::new(2, 2)), &mut dst, Point::new(1, 1), BlitMode::Copy);
+
+        assert_eq!(dst.get(Point::new(0, 0)), RGB::new(0, 0, 0));
+        assert_eq!(dst.get(Point::new(1, 1)), RGB::new(248, 0, 0));
+        assert_eq!(dst.get(Point::new(2, 1)), RGB::new(0, 252, 0));
+        assert_eq!(dst.get(Point::new(1, 2)), RGB::new(0, 0, 248));
+        assert_eq!(dst.get(Point::new(2, 2)), RGB::new(248, 252, 248));
+
+        dst.fill(RGB::new(0, 0, 0));
+
+        blit(&src, Rect::new(Point::new(0, 0), Size::new(2, 2)), &mut dst, Point::new(0, 0), BlitMode::Alpha(128));
+
+        assert_eq!(dst.get(Point::new(0, 0)), RGB::new(248, 0, 0));
+        assert_eq!(dst.get(Point::new(1, 0)), RGB::new(0, 0, 0));
+        assert_eq!(dst.get(Point::new(0, 1)), RGB::new(0, 0, 248));
+        assert_eq!(dst.get(Point::new(1, 1)), RGB::new(0, 0, 0));
+    }
+
+    #[test]
+    fn blit_color_key() {
+        let src = PixelView::new(Size::new(3, 1), (format::GS8, [1, 0, 3]));
+        let mut dst = PixelView::new(Size::new(3, 2), (format::GS8, [9u8; 6]))
+            .with_orientation(Orientation::Rotate90);
+
+        blit(&src, Rect::new(Point::new(0, 0), Size::new(3, 1)), &mut dst, Point::new(0, 1), BlitMode::ColorKey(GS::new(0)));
+
+        assert_eq!(dst.get(Point::new(0, 1)), GS::new(1));
+        assert_eq!(dst.get(Point::new(1, 1)), GS::new(9));
+        assert_eq!(dst.unwrap().1, [9, 1, 9, 9, 9, 9]);
+    }
+
+    #[test]
+    fn blit_clip() {
+        let src = PixelView::new(Size::new(4, 1), (format::GS8, [1, 2, 3, 4]));
+        let mut dst = PixelView::new(Size::new(3, 2), (format::GS8, [0u8; 6]));
+
+        blit(&src, Rect::new(Point::new(1, 0), Size::new(8, 8)), &mut dst, Point::new(1, 1), BlitMode::Copy);
+
+        assert_eq!(dst.unwrap().1, [0, 0, 0, 0, 2, 3]);
+    }
+
+    #[test]
+    fn blit_blend_over() {
+        let src = PixelView::new(Size::new(2, 1), (format::GSA88, [255, 255, 255, 0]));
+        let mut dst = PixelView::new(Size::new(2, 2), (format::GS8, [100u8; 4]));
+
+        blit_blend(&src, Rect::new(Point::new(0, 0), Size::new(2, 1)), &mut dst, Point::new(0, 1), BlendOp::SrcOver, 51);
+
+        assert_eq!(dst.unwrap().1, [100, 100, 131, 100]);
+    }
+
+    #[test]
+    fn blit_dither() {
+        let src = PixelView::new(Size::new(4, 4), (format::GS8, [100u8; 16]));
+        let mut dst = PixelView::new(Size::new(4, 4), (<format::GS2>::DEFAULT, [0u8; 4]));
+
+        blit_dithered(&src, Rect::new(Point::new(0, 0), Size::new(4, 4)), &mut dst, Point::new(0, 0), Bayer::Bayer4);
+
+        let mut sum = 0;
+        for y in 0..4 {
+            for x in 0..4 {
+                sum += dst.get(Point::new(x, y)).v as u32;
+            }
+        }
+
+        assert_eq!(dst.get(Point::new(0, 0)), GS::new(85));
+        assert_eq!(dst.get(Point::new(0, 3)), GS::new(170));
+        assert_eq!(sum, 85 * 13 + 170 * 3);
+
+        let src = PixelView::new(Size::new(2, 1), (format::RGB888, [255, 0, 0, 0, 0, 255]));
+        let mut dst = PixelView::new(Size::new(2, 1), (format::RGB332, [0u8; 2]));
+
+        blit_dithered(&src, Rect::new(Point::new(0, 0), Size::new(2, 1)), &mut dst, Point::new(0, 0), Bayer::Bayer2);
+
+        assert_eq!(dst.unwrap().1, [0b1110_0000, 0b0000_0011]);
+    }
+
+    #[test]
+    fn copy_bytes() {
+        let src = PixelView::new(Size::new(2, 2), (format::RGB565, [1u8, 2, 3, 4, 5, 6, 7, 8]));
+        let mut dst = PixelView::new(Size::new(3, 3), (format::RGB565, [0u8; 18]));
+
+        copy(&src, Rect::new(Point::new(0, 0), Size::new(2, 2)), &mut dst, Point::new(1, 0));
+
+        assert_eq!(dst.unwrap().1, [0, 0, 1, 2, 3, 4, 0, 0, 5, 6, 7, 8, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn copy_bits() {
+        let src = PixelView::new(Size::new(8, 1), (<format::BW1>::DEFAULT, [0b1010_0101u8]));
+        let mut dst = PixelView::new(Size::new(8, 8), (<format::BW1>::DEFAULT, [0u8; 8]))
+            .with_layout(Paged);
+
+        copy(&src, Rect::new(Point::new(1, 0), Size::new(4, 1)), &mut dst, Point::new(0, 1));
+
+        assert_eq!(dst.unwrap().1, [0, 0b10, 0, 0, 0, 0, 0, 0]);
+
+        let mut dst = PixelView::new(Size::new(8, 1), (<format::BW1>::DEFAULT, [0u8; 1]));
+
+        copy(&src, Rect::new(Point::new(1, 0), Size::new(4, 1)), &mut dst, Point::new(2, 0));
+
+        assert_eq!(dst.unwrap().1, [0b0000_1000]);
+    }
+}
@@ -0,0 +1,70 @@
+use crate::{Point, Size, ColorFmt, ColorSet, ColorVec};
+use super::{PixelView, Linear};
+
+/// Heap-allocated buffer for pixels
+pub type PixelBuffer<Fmt> = PixelView<ColorVec<Fmt>, Linear>;
+
+impl<Fmt> PixelView<ColorVec<Fmt>, Linear>
+where
+    Fmt: ColorFmt,
+{
+    /// Allocate buffer for pixels of specified size filled by zero bytes
+    pub fn alloc(size: Size<usize>, format: Fmt) -> Self {
+        Self::new(size, ColorVec::new_with_format(format, size.w * size.h))
+    }
+
+    /// Allocate buffer for pixels of specified size filled by color
+    pub fn alloc_filled(size: Size<usize>, format: Fmt, color: Fmt::ColorType) -> Self
+    where
+        Fmt: ColorSet,
+        Fmt::ColorType: Clone,
+    {
+        Self::new(size, ColorVec::new_filled_with_format(format, size.w * size.h, color))
+    }
+
+    /// Change size of buffer
+    ///
+    /// The size is logical, so it will be swapped for rotated views.
+    /// The whole view is reset to buffer and filled by color.
+    pub fn resize(&mut self, size: Size<usize>, color: Fmt::ColorType)
+    where
+        Fmt: ColorSet,
+        Fmt::ColorType: Clone,
+    {
+        self.size = self.orient.map_size(size);
+        self.stride = self.size.w;
+        self.origin = Point::new(0, 0);
+
+        self.data.resize(0, color.clone());
+        self.data.resize(size.w * size.h, color);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{format, Rect, Orientation, ConstDefault, GS};
+
+    #[test]
+    fn alloc() {
+        let mut buffer = PixelBuffer::alloc(Size::new(3, 2), format::GS8);
+
+        buffer.fill_rect(Rect::new(Point::new(1, 1), Size::new(2, 1)), GS::new(9));
+
+        assert_eq!(buffer.size(), Size::new(3, 2));
+        assert_eq!(buffer.clone().unwrap().into_bytes(), [0, 0, 0, 0, 9, 9]);
+    }
+
+    #[test]
+    fn resize() {
+        let mut buffer = PixelBuffer::alloc_filled(Size::new(2, 2), <format::GS4>::DEFAULT, GS::new(17))
+            .with_orientation(Orientation::Rotate90);
+
+        buffer.resize(Size::new(3, 1), GS::new(34));
+
+        assert_eq!(buffer.size(), Size::new(3, 1));
+        assert_eq!(buffer.stride(), 1);
+        assert_eq!(buffer.get(Point::new(2, 0)), GS::new(34));
+        assert_eq!(buffer.unwrap().into_bytes(), [0x22, 0x02]);
+    }
+}
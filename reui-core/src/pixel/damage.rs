@@ -0,0 +1,280 @@
+use crate::{ConstDefault, Point, Rect, Area, ColorFmt, ColorBufMut, Paint, Blend, RGBA};
+use super::{PixelView, PixelLayout, Linear};
+
+/// Number of pixels in area
+fn pixels(area: &Area<usize>) -> usize {
+    if area.is_empty() {
+        0
+    } else {
+        let size = area.size();
+        size.w * size.h
+    }
+}
+
+/// Number of extra pixels which is covered by union of areas
+fn waste(a: &Area<usize>, b: &Area<usize>) -> usize {
+    (pixels(&(*a | *b)) + pixels(&(*a & *b))).saturating_sub(pixels(a) + pixels(b))
+}
+
+/// Set of changed areas
+///
+/// Keeps up to `N` areas. The overlapping areas and areas which can be joined without extra pixels
+/// are merged. When there is no room for new area it is merged with area which gives least extra pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Damage<const N: usize> {
+    areas: [Area<usize>; N],
+    len: usize,
+}
+
+impl<const N: usize> ConstDefault for Damage<N> {
+    const DEFAULT: Self = Self::new();
+}
+
+impl<const N: usize> Default for Damage<N> {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl<const N: usize> Damage<N> {
+    /// Capacity checked to be non-zero
+    const CAPACITY: usize = {
+        assert!(N > 0, "Damage capacity should be non-zero");
+        N
+    };
+
+    /// Create empty set
+    ///
+    /// Fails to compile when capacity is zero.
+    pub const fn new() -> Self {
+        let _ = Self::CAPACITY;
+        Self { areas: [Area { lt: Point::new(0, 0), rb: Point::new(0, 0) }; N], len: 0 }
+    }
+
+    /// Number of areas
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Forget all areas
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Get changed areas
+    pub fn areas(&self) -> &[Area<usize>] {
+        &self.areas[..self.len]
+    }
+
+    /// Get changed areas as rectangles
+    pub fn rects(&self) -> impl Iterator<Item = Rect<usize>> + '_ {
+        self.areas().iter().copied().map(Rect::from)
+    }
+
+    /// Add changed rectangle
+    pub fn add(&mut self, rect: Rect<usize>) {
+        self.add_area(Area::from(rect));
+    }
+
+    /// Add changed area
+    pub fn add_area(&mut self, mut area: Area<usize>) {
+        if area.is_empty() {
+            return;
+        }
+
+        let mut index = 0;
+
+        while index < self.len {
+            let other = self.areas[index];
+
+            if !(other & area).is_empty() || waste(&other, &area) == 0 {
+                area |= self.remove(index);
+                // merged area may now touch areas which was checked before
+                index = 0;
+            } else {
+                index += 1;
+            }
+        }
+
+        if self.len == Self::CAPACITY {
+            let index = (0..self.len).min_by_key(|index| waste(&self.areas[*index], &area))
+                .unwrap_or(0);
+            area |= self.remove(index);
+            return self.add_area(area);
+        }
+
+        self.areas[self.len] = area;
+        self.len += 1;
+    }
+
+    fn remove(&mut self, index: usize) -> Area<usize> {
+        let area = self.areas[index];
+        self.len -= 1;
+        self.areas[index] = self.areas[self.len];
+        area
+    }
+}
+
+/// Pixel view which tracks changed areas
+///
+/// The drawing operations marks touched areas, so driver can flush only changed parts of view.
+#[derive(Debug, Clone)]
+pub struct DamageTracker<Buf, Layout = Linear, const N: usize = 8> {
+    view: PixelView<Buf, Layout>,
+    damage: Damage<N>,
+}
+
+impl<Buf, Layout, const N: usize> DamageTracker<Buf, Layout, N> {
+    pub fn new(view: PixelView<Buf, Layout>) -> Self {
+        Self { view, damage: Damage::new() }
+    }
+
+    pub fn view(&self) -> &PixelView<Buf, Layout> {
+        &self.view
+    }
+
+    pub fn damage(&self) -> &Damage<N> {
+        &self.damage
+    }
+
+    pub fn unwrap(self) -> PixelView<Buf, Layout> {
+        self.view
+    }
+
+    /// Mark rectangle of view as changed
+    ///
+    /// The specified rectangle is clipped by view bounds.
+    pub fn invalidate(&mut self, rect: Rect<usize>) {
+        let size = self.view.size();
+        let (l, t) = (rect.left().min(size.w), rect.top().min(size.h));
+        let (r, b) = (rect.right().min(size.w), rect.bottom().min(size.h));
+
+        self.damage.add_area(Area::from((l, t, r, b)));
+    }
+
+    /// Mark whole view as changed
+    pub fn invalidate_all(&mut self) {
+        self.invalidate(Rect::new(Point::new(0, 0), self.view.size()));
+    }
+
+    /// Get mutable view of the part of view and mark it as changed
+    pub fn sub_view_mut(&mut self, rect: Rect<usize>) -> PixelView<&mut Buf, &Layout> {
+        self.invalidate(rect);
+        self.view.sub_view_mut(rect)
+    }
+
+    /// Pass changed parts of view to function and forget changes
+    pub fn flush<Flush>(&mut self, mut flush: Flush)
+    where
+        Flush: FnMut(Rect<usize>, PixelView<&Buf, &Layout>),
+    {
+        for rect in self.damage.rects() {
+            flush(rect, self.view.sub_view(rect));
+        }
+
+        self.damage.clear();
+    }
+}
+
+impl<Buf, Layout, const N: usize> DamageTracker<Buf, Layout, N>
+where
+    Buf: ColorBufMut,
+    Layout: PixelLayout,
+{
+    pub fn set(&mut self, point: Point<usize>, color: <Buf::ColorFmt as ColorFmt>::ColorType) {
+        self.invalidate(Rect::new(point, (1, 1).into()));
+        self.view.set(point, color)
+    }
+
+    /// Fill rectangle by single color
+    pub fn fill_rect(&mut self, rect: Rect<usize>, color: <Buf::ColorFmt as ColorFmt>::ColorType)
+    where
+        <Buf::ColorFmt as ColorFmt>::ColorType: Clone,
+    {
+        self.invalidate(rect);
+        self.view.fill_rect(rect, color)
+    }
+
+    /// Fill whole view by single color
+    pub fn fill(&mut self, color: <Buf::ColorFmt as ColorFmt>::ColorType)
+    where
+        <Buf::ColorFmt as ColorFmt>::ColorType: Clone,
+    {
+        self.invalidate_all();
+        self.view.fill(color)
+    }
+
+    /// Paint rectangle of view using paint source
+    pub fn paint_rect<P: Paint>(&mut self, rect: Rect<usize>, paint: &P)
+    where
+        <Buf::ColorFmt as ColorFmt>::ColorType: Blend<RGBA>,
+    {
+        self.invalidate(rect);
+        self.view.paint_rect(rect, paint)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{format, Size, GS};
+
+    fn rect(l: usize, t: usize, r: usize, b: usize) -> Rect<usize> {
+        (l, t, r, b).into()
+    }
+
+    #[test]
+    fn merge() {
+        let mut damage = Damage::<2>::new();
+
+        damage.add(rect(0, 0, 2, 2));
+        damage.add(rect(1, 1, 3, 3));
+
+        assert!(damage.rects().eq([rect(0, 0, 3, 3)].iter().cloned()));
+
+        // joined without extra pixels
+        damage.add(rect(3, 0, 5, 3));
+        damage.add(rect(8, 8, 8, 9));
+
+        assert!(damage.rects().eq([rect(0, 0, 5, 3)].iter().cloned()));
+
+        damage.add(rect(10, 0, 11, 1));
+        damage.add(rect(0, 4, 5, 5));
+
+        assert_eq!(damage.len(), 2);
+        assert!(damage.rects().eq([rect(10, 0, 11, 1), rect(0, 0, 5, 5)].iter().cloned()));
+
+        damage.clear();
+
+        assert!(damage.is_empty());
+    }
+
+    #[test]
+    fn tracker() {
+        let view = PixelView::new(Size::new(8, 4), (format::GS8, [0u8; 32]));
+        let mut tracker = DamageTracker::<_, _, 4>::new(view);
+
+        tracker.set(Point::new(7, 3), GS::new(1));
+        tracker.fill_rect(rect(1, 1, 3, 10), GS::new(2));
+        tracker.sub_view_mut(rect(5, 0, 6, 1)).fill(GS::new(3));
+
+        let mut flushed = [Rect::default(); 4];
+        let mut count = 0;
+
+        tracker.flush(|rect, view| {
+            assert_eq!(view.size(), rect.size());
+            flushed[count] = rect;
+            count += 1;
+        });
+
+        flushed[..count].sort_by_key(|rect| (rect.left(), rect.top()));
+
+        assert_eq!(flushed[..count], [rect(1, 1, 3, 4), rect(5, 0, 6, 1), rect(7, 3, 8, 4)]);
+        assert!(tracker.damage().is_empty());
+        assert_eq!(tracker.view().get(Point::new(2, 3)), GS::new(2));
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{ConstDefault, Rect, Area, ColorBufMut};
+use super::{PixelView, PixelLayout, Linear, copy};
+
+/// What happens with back buffer after swapping
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwapMode {
+    /// Back buffer keeps the frame before completed, so it should be fully redrawn
+    Flip,
+    /// Changed area of completed frame is copied to back buffer
+    Copy,
+}
+
+impl ConstDefault for SwapMode {
+    const DEFAULT: Self = SwapMode::Flip;
+}
+
+impl Default for SwapMode {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Pair of pixel views for drawing to one while another is flushed
+///
+/// The changed area of back buffer is tracked to flush and copy only it.
+#[derive(Debug, Clone)]
+pub struct DoubleBuffer<Buf, Layout = Linear> {
+    views: [PixelView<Buf, Layout>; 2],
+    /// Index of front view
+    front: usize,
+    mode: SwapMode,
+    /// Changed area of back view
+    dirty: Option<Area<usize>>,
+}
+
+impl<Buf, Layout> DoubleBuffer<Buf, Layout> {
+    /// Create double buffer from front and back views
+    pub fn new(front: PixelView<Buf, Layout>, back: PixelView<Buf, Layout>) -> Self {
+        Self { views: [front, back], front: 0, mode: SwapMode::DEFAULT, dirty: None }
+    }
+
+    /// Set swapping mode
+    pub fn with_mode(mut self, mode: SwapMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn mode(&self) -> SwapMode {
+        self.mode
+    }
+
+    /// Get completed frame
+    pub fn front(&self) -> &PixelView<Buf, Layout> {
+        &self.views[self.front]
+    }
+
+    /// Get frame which is drawn
+    pub fn back(&self) -> &PixelView<Buf, Layout> {
+        &self.views[1 - self.front]
+    }
+
+    /// Get frame for drawing
+    ///
+    /// The changes should be marked using [`DoubleBuffer::invalidate`].
+    pub fn back_mut(&mut self) -> &mut PixelView<Buf, Layout> {
+        &mut self.views[1 - self.front]
+    }
+
+    /// Get part of frame for drawing and mark it as changed
+    pub fn back_sub_view_mut(&mut self, rect: Rect<usize>) -> PixelView<&mut Buf, &Layout> {
+        self.invalidate(rect);
+        self.views[1 - self.front].sub_view_mut(rect)
+    }
+
+    /// Mark area of back frame as changed
+    pub fn invalidate(&mut self, rect: Rect<usize>) {
+        if rect.is_collapsed() {
+            return;
+        }
+
+        let area = Area::from(rect);
+
+        self.dirty = Some(match self.dirty {
+            Some(dirty) => dirty | area,
+            None => area,
+        });
+    }
+
+    /// Get changed area of back frame
+    pub fn dirty(&self) -> Option<Rect<usize>> {
+        self.dirty.map(Rect::from)
+    }
+
+    /// Unwrap front and back views
+    pub fn unwrap(self) -> (PixelView<Buf, Layout>, PixelView<Buf, Layout>) {
+        let [first, second] = self.views;
+
+        if self.front == 0 {
+            (first, second)
+        } else {
+            (second, first)
+        }
+    }
+}
+
+impl<Buf, Layout> DoubleBuffer<Buf, Layout>
+where
+    Buf: ColorBufMut,
+    Layout: PixelLayout,
+{
+    /// Make back frame completed
+    ///
+    /// Returns changed area of completed frame which should be flushed.
+    pub fn swap(&mut self) -> Option<Rect<usize>> {
+        let dirty = self.dirty();
+
+        self.front = 1 - self.front;
+        self.dirty = None;
+
+        if let (SwapMode::Copy, Some(rect)) = (self.mode, dirty) {
+            let [first, second] = &mut self.views;
+            let (front, back) = if self.front == 0 { (first, second) } else { (second, first) };
+
+            copy(front, rect, back, rect.left_top());
+        }
+
+        dirty
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{format, Point, Size, GS};
+
+    fn buffer() -> DoubleBuffer<(format::GS8, [u8; 6])> {
+        DoubleBuffer::new(
+            PixelView::new(Size::new(3, 2), (format::GS8, [0; 6])),
+            PixelView::new(Size::new(3, 2), (format::GS8, [0; 6])),
+        )
+    }
+
+    #[test]
+    fn flip() {
+        let mut buffer = buffer();
+
+        buffer.back_mut().set(Point::new(1, 1), GS::new(5));
+        buffer.invalidate(Rect::new(Point::new(1, 1), Size::new(1, 1)));
+        buffer.invalidate(Rect::new(Point::new(0, 0), Size::new(1, 1)));
+
+        assert_eq!(buffer.dirty(), Some(Rect::new(Point::new(0, 0), Size::new(2, 2))));
+        assert_eq!(buffer.swap(), Some(Rect::new(Point::new(0, 0), Size::new(2, 2))));
+        assert_eq!(buffer.dirty(), None);
+        assert_eq!(buffer.front().get(Point::new(1, 1)), GS::new(5));
+        assert_eq!(buffer.back().get(Point::new(1, 1)), GS::new(0));
+
+        assert_eq!(buffer.swap(), None);
+        assert_eq!(buffer.front().get(Point::new(1, 1)), GS::new(0));
+    }
+
+    #[test]
+    fn copy_back() {
+        let mut buffer = buffer().with_mode(SwapMode::Copy);
+
+        buffer.back_sub_view_mut(Rect::new(Point::new(1, 0), Size::new(2, 1))).fill(GS::new(7));
+
+        assert_eq!(buffer.swap(), Some(Rect::new(Point::new(1, 0), Size::new(2, 1))));
+
+        buffer.back_sub_view_mut(Rect::new(Point::new(0, 1), Size::new(1, 1))).fill(GS::new(9));
+        buffer.swap();
+
+        let (front, back) = buffer.unwrap();
+
+        assert_eq!(front.unwrap().1, [0, 7, 7, 9, 0, 0]);
+        assert_eq!(back.unwrap().1, [0, 7, 7, 9, 0, 0]);
+    }
+}
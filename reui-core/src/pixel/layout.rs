@@ -0,0 +1,155 @@
+use core::ops::Range;
+use crate::{ConstDefault, Point};
+
+/// Pixel layout
+///
+/// Layout determines how pixels of two-dimensional view maps to colors in buffer.
+pub trait PixelLayout {
+    /// Get index of color in buffer for pixel at specified point
+    ///
+    /// The `stride` is a distance between rows in pixels and `bits` is a packed size of color.
+    fn color_index(&self, point: Point<usize>, stride: usize, bits: usize) -> usize;
+
+    /// Get stride in pixels from stride in bytes
+    ///
+    /// Panics when stride in bytes doesn't hold whole number of pixels.
+    fn stride_from_bytes(&self, bytes: usize, bits: usize) -> usize {
+        assert!((bytes * 8).is_multiple_of(bits), "Stride in bytes should hold whole number of pixels");
+        bytes * 8 / bits
+    }
+
+    /// Get range of colors in buffer for horizontal span of pixels
+    ///
+    /// Returns `None` when pixels of span isn't stored contiguously.
+    fn color_span(&self, _point: Point<usize>, _length: usize, _stride: usize, _bits: usize) -> Option<Range<usize>> {
+        None
+    }
+}
+
+/// Linear layout
+///
+/// Pixels stored row by row from left to right and from top to bottom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Linear;
+
+impl ConstDefault for Linear {
+    const DEFAULT: Self = Self;
+}
+
+impl PixelLayout for Linear {
+    fn color_index(&self, point: Point<usize>, stride: usize, _bits: usize) -> usize {
+        point.x + point.y * stride
+    }
+
+    fn color_span(&self, point: Point<usize>, length: usize, stride: usize, bits: usize) -> Option<Range<usize>> {
+        let index = self.color_index(point, stride, bits);
+        Some(index..index + length)
+    }
+}
+
+/// Paged layout with vertical bytes
+///
+/// View is splitted to horizontal pages which height is the number of colors per byte.
+/// Each byte holds the column of vertical pixels in page, columns stored from left to right
+/// and pages stored from top to bottom. The stride is a number of columns in page.
+///
+/// This is a layout of GDDRAM in SSD1306/SH1106-like monochrome displays.
+///
+/// Page of 1-bit pixels (`format::BW1`):
+///
+/// `0b76543210 ...` (the digits is a pixel rows in page)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Paged;
+
+impl ConstDefault for Paged {
+    const DEFAULT: Self = Self;
+}
+
+impl Paged {
+    fn colors_per_byte(bits: usize) -> usize {
+        if bits < 8 { 8 / bits } else { 1 }
+    }
+}
+
+impl PixelLayout for Paged {
+    fn color_index(&self, point: Point<usize>, stride: usize, bits: usize) -> usize {
+        let cpb = Self::colors_per_byte(bits);
+        ((point.y / cpb) * stride + point.x) * cpb + point.y % cpb
+    }
+
+    fn stride_from_bytes(&self, bytes: usize, bits: usize) -> usize {
+        let cpb = Self::colors_per_byte(bits);
+        assert!((bytes * 8).is_multiple_of(bits * cpb), "Stride in bytes should hold whole number of columns");
+        bytes * 8 / bits / cpb
+    }
+}
+
+impl<Layout> PixelLayout for &Layout
+where
+    Layout: PixelLayout,
+{
+    fn color_index(&self, point: Point<usize>, stride: usize, bits: usize) -> usize {
+        (*self).color_index(point, stride, bits)
+    }
+
+    fn stride_from_bytes(&self, bytes: usize, bits: usize) -> usize {
+        (*self).stride_from_bytes(bytes, bits)
+    }
+
+    fn color_span(&self, point: Point<usize>, length: usize, stride: usize, bits: usize) -> Option<Range<usize>> {
+        (*self).color_span(point, length, stride, bits)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn linear() {
+        assert_eq!(Linear.color_index(Point::new(0, 0), 16, 1), 0);
+        assert_eq!(Linear.color_index(Point::new(3, 0), 16, 1), 3);
+        assert_eq!(Linear.color_index(Point::new(3, 2), 16, 1), 35);
+        assert_eq!(Linear.color_index(Point::new(3, 2), 16, 16), 35);
+    }
+
+    #[test]
+    fn paged() {
+        assert_eq!(Paged.color_index(Point::new(0, 0), 16, 1), 0);
+        assert_eq!(Paged.color_index(Point::new(0, 7), 16, 1), 7);
+        assert_eq!(Paged.color_index(Point::new(1, 0), 16, 1), 8);
+        assert_eq!(Paged.color_index(Point::new(3, 10), 16, 1), 19 * 8 + 2);
+        assert_eq!(Paged.color_index(Point::new(3, 10), 16, 2), 35 * 4 + 2);
+        assert_eq!(Paged.color_index(Point::new(3, 10), 16, 8), 163);
+    }
+
+    #[test]
+    fn color_span() {
+        assert_eq!(Linear.color_span(Point::new(3, 2), 5, 16, 1), Some(35..40));
+        assert_eq!(Paged.color_span(Point::new(3, 2), 5, 16, 1), None);
+    }
+
+    #[test]
+    fn stride_from_bytes() {
+        assert_eq!(Linear.stride_from_bytes(16, 1), 128);
+        assert_eq!(Linear.stride_from_bytes(16, 4), 32);
+        assert_eq!(Linear.stride_from_bytes(16, 16), 8);
+        assert_eq!(Paged.stride_from_bytes(16, 1), 16);
+        assert_eq!(Paged.stride_from_bytes(16, 4), 16);
+        assert_eq!(Paged.stride_from_bytes(16, 16), 8);
+        assert_eq!(Linear.stride_from_bytes(12, 24), 4);
+        assert_eq!(Paged.stride_from_bytes(9, 24), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn stride_from_bytes_partial() {
+        Linear.stride_from_bytes(8, 24);
+    }
+
+    #[test]
+    #[should_panic]
+    fn stride_from_bytes_partial_paged() {
+        Paged.stride_from_bytes(3, 16);
+    }
+}
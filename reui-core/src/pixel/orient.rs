@@ -0,0 +1,133 @@
+use crate::{ConstDefault, Point, Size, Rect};
+
+/// Orientation of pixel view
+///
+/// Orientation determines how logical coordinates of view maps to physical coordinates in buffer.
+/// Rotations is clockwise, so `Rotate90` means that the logical top side of view
+/// is the physical right side of buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// No transformation
+    Rotate0,
+    /// Rotated by 90 degrees clockwise
+    Rotate90,
+    /// Rotated by 180 degrees
+    Rotate180,
+    /// Rotated by 270 degrees clockwise
+    Rotate270,
+    /// Mirrored horizontally
+    FlipH,
+    /// Mirrored vertically
+    FlipV,
+}
+
+impl ConstDefault for Orientation {
+    const DEFAULT: Self = Orientation::Rotate0;
+}
+
+impl Default for Orientation {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl Orientation {
+    /// Check that orientation swaps width and height
+    pub fn is_transposed(&self) -> bool {
+        matches!(self, Orientation::Rotate90 | Orientation::Rotate270)
+    }
+
+    /// Check that orientation keeps pixels in rows from left to right
+    pub fn keeps_rows(&self) -> bool {
+        matches!(self, Orientation::Rotate0 | Orientation::FlipV)
+    }
+
+    /// Convert logical size to physical and vice versa
+    pub fn map_size(&self, size: Size<usize>) -> Size<usize> {
+        if self.is_transposed() {
+            Size::new(size.h, size.w)
+        } else {
+            size
+        }
+    }
+
+    /// Convert logical point to physical using physical size of view
+    pub fn map_point(&self, point: Point<usize>, size: Size<usize>) -> Point<usize> {
+        use Orientation::*;
+
+        let Point { x, y } = point;
+        let Size { w, h } = size;
+
+        match self {
+            Rotate0 => Point::new(x, y),
+            Rotate90 => Point::new(w - 1 - y, x),
+            Rotate180 => Point::new(w - 1 - x, h - 1 - y),
+            Rotate270 => Point::new(y, h - 1 - x),
+            FlipH => Point::new(w - 1 - x, y),
+            FlipV => Point::new(x, h - 1 - y),
+        }
+    }
+
+    /// Convert logical rectangle to physical using physical size of view
+    ///
+    /// The rectangle should be inside of view.
+    pub fn map_rect(&self, rect: Rect<usize>, size: Size<usize>) -> Rect<usize> {
+        use Orientation::*;
+
+        let (l, t, r, b) = (rect.left(), rect.top(), rect.right(), rect.bottom());
+        let Size { w, h } = size;
+
+        match self {
+            Rotate0 => (l, t, r, b),
+            Rotate90 => (w - b, l, w - t, r),
+            Rotate180 => (w - r, h - b, w - l, h - t),
+            Rotate270 => (t, h - r, b, h - l),
+            FlipH => (w - r, t, w - l, b),
+            FlipV => (l, h - b, r, h - t),
+        }.into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn map_point() {
+        use Orientation::*;
+
+        let size = Size::new(4, 3);
+        let point = Point::new(1, 0);
+
+        assert_eq!(Rotate0.map_point(point, size), Point::new(1, 0));
+        assert_eq!(Rotate90.map_point(point, size), Point::new(3, 1));
+        assert_eq!(Rotate180.map_point(point, size), Point::new(2, 2));
+        assert_eq!(Rotate270.map_point(point, size), Point::new(0, 1));
+        assert_eq!(FlipH.map_point(point, size), Point::new(2, 0));
+        assert_eq!(FlipV.map_point(point, size), Point::new(1, 2));
+    }
+
+    #[test]
+    fn map_rect() {
+        use Orientation::*;
+
+        let size = Size::new(4, 3);
+
+        for orient in &[Rotate0, Rotate90, Rotate180, Rotate270, FlipH, FlipV] {
+            let logical = orient.map_size(size);
+            let rect = Rect::new(Point::new(1, 1), Size::new(logical.w - 1, 1));
+            let mapped = orient.map_rect(rect, size);
+
+            assert_eq!(mapped.size(), orient.map_size(rect.size()));
+
+            for x in rect.left()..rect.right() {
+                for y in rect.top()..rect.bottom() {
+                    let point = orient.map_point(Point::new(x, y), size);
+
+                    assert!(point.x >= mapped.left() && point.x < mapped.right());
+                    assert!(point.y >= mapped.top() && point.y < mapped.bottom());
+                }
+            }
+        }
+    }
+}
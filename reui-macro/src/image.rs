@@ -1,9 +1,13 @@
 mod attr;
 mod item;
 mod data;
+mod dither;
+mod quantize;
 mod macros;
 
 pub use self::attr::*;
 pub use self::item::*;
 pub use self::data::*;
+pub use self::dither::*;
+pub use self::quantize::*;
 pub use self::macros::*;
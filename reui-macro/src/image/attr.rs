@@ -1,17 +1,47 @@
 use syn::{
     Result,
+    Error,
     LitStr,
+    Ident,
+    token::{Comma, Eq},
     parse::{Parse, ParseStream},
 };
 
 pub struct ImageAttr {
     pub path: LitStr,
+    /// Target color format
+    pub format: Option<Ident>,
+    /// Dithering method for reducing colors to target format
+    pub dither: Option<Ident>,
+    /// Bit order of formats which packs several colors into single byte
+    pub order: Option<Ident>,
 }
 
 impl Parse for ImageAttr {
     fn parse(input: ParseStream) -> Result<Self> {
+        let path = input.parse()?;
+        let mut format = None;
+        let mut dither = None;
+        let mut order = None;
+
+        while input.parse::<Option<Comma>>()?.is_some() && !input.is_empty() {
+            let key = input.parse::<Ident>()?;
+            input.parse::<Eq>()?;
+            let value = input.parse::<Ident>()?;
+
+            match key.to_string().as_str() {
+                "format" => format = Some(value),
+                "dither" => dither = Some(value),
+                "order" => order = Some(value),
+                _ => return Err(Error::new(key.span(), "Unknown option, expected `format`, `dither` or `order`")),
+            }
+        }
+
         Ok(Self {
-            path: input.parse()?,
+            path,
+            format,
+            dither,
+            order,
         })
     }
 }
@@ -26,5 +56,28 @@ mod test {
         let params: ImageAttr = parse_str(r#""path/to/icon.png""#).unwrap();
 
         assert_eq!(params.path.value(), "path/to/icon.png");
+        assert!(params.format.is_none());
+        assert!(params.dither.is_none());
+        assert!(params.order.is_none());
+    }
+
+    #[test]
+    fn options() {
+        let params: ImageAttr = parse_str(r#""path/to/icon.png", format = RGB565, dither = FloydSteinberg"#).unwrap();
+
+        assert_eq!(params.path.value(), "path/to/icon.png");
+        assert_eq!(params.format.unwrap().to_string(), "RGB565");
+        assert_eq!(params.dither.unwrap().to_string(), "FloydSteinberg");
+
+        let params: ImageAttr = parse_str(r#""path/to/icon.png", format = BW1, order = MSB"#).unwrap();
+
+        assert_eq!(params.format.unwrap().to_string(), "BW1");
+        assert_eq!(params.order.unwrap().to_string(), "MSB");
+    }
+
+    #[test]
+    fn unknown_option() {
+        assert!(parse_str::<ImageAttr>(r#""path/to/icon.png", size = 2"#).is_err());
+        assert!(parse_str::<ImageAttr>(r#""path/to/icon.png", color = RGB565"#).is_err());
     }
 }
@@ -13,6 +13,7 @@ pub struct Size {
 
 #[derive(Clone)]
 pub enum ColorFmt {
+    BW(u8),
     GS(u8),
     GSA(u8, u8),
     RGB(u8, u8, u8),
@@ -48,6 +49,39 @@ impl TryFrom<&png::ColorMode> for ColorFmt {
     }
 }
 
+impl ColorFmt {
+    /// Get format by name of type
+    ///
+    /// Indexed formats have empty palette which should be filled from image colors.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "BW1" => ColorFmt::BW(1),
+            "GS1" => ColorFmt::GS(1),
+            "GS2" => ColorFmt::GS(2),
+            "GS4" => ColorFmt::GS(4),
+            "GS8" => ColorFmt::GS(8),
+            "GSA11" => ColorFmt::GSA(1, 1),
+            "GSA22" => ColorFmt::GSA(2, 2),
+            "GSA31" => ColorFmt::GSA(3, 1),
+            "GSA44" => ColorFmt::GSA(4, 4),
+            "GSA71" => ColorFmt::GSA(7, 1),
+            "GSA88" => ColorFmt::GSA(8, 8),
+            "RGB332" => ColorFmt::RGB(3, 3, 2),
+            "RGB444" => ColorFmt::RGB(4, 4, 4),
+            "RGB565" => ColorFmt::RGB(5, 6, 5),
+            "RGB888" => ColorFmt::RGB(8, 8, 8),
+            "RGBA4444" => ColorFmt::RGBA(4, 4, 4, 4),
+            "RGBA5551" => ColorFmt::RGBA(5, 5, 5, 1),
+            "RGBA8888" => ColorFmt::RGBA(8, 8, 8, 8),
+            "IDX1" => ColorFmt::IDX(1, Vec::new()),
+            "IDX2" => ColorFmt::IDX(2, Vec::new()),
+            "IDX4" => ColorFmt::IDX(4, Vec::new()),
+            "IDX8" => ColorFmt::IDX(8, Vec::new()),
+            _ => return None,
+        })
+    }
+}
+
 #[derive(Clone)]
 pub enum ColorData {
     GS(Vec<base::GS>),
@@ -123,4 +157,17 @@ impl ImageData {
             pixels,
         })
     }
+
+    /// Get pixels as RGBA colors
+    pub fn colors(&self) -> Vec<base::RGBA> {
+        match (&self.pixels, &self.format) {
+            (ColorData::GS(pixels), _) => pixels.iter().map(|c| (*c).into()).collect(),
+            (ColorData::GSA(pixels), _) => pixels.iter().map(|c| (*c).into()).collect(),
+            (ColorData::RGB(pixels), _) => pixels.iter().map(|c| (*c).into()).collect(),
+            (ColorData::RGBA(pixels), _) => pixels.clone(),
+            (ColorData::IDX(indexes), ColorFmt::IDX(_, palette)) =>
+                indexes.iter().map(|i| palette.get(*i as usize).cloned().unwrap_or_default()).collect(),
+            (ColorData::IDX(_), _) => unreachable!(),
+        }
+    }
 }
@@ -0,0 +1,165 @@
+use reui_core as base;
+use base::{ColorSet, ColorDistance, Point, RGBA};
+
+use super::Size;
+
+/// Dithering method
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Dither {
+    /// Reduce colors to nearest levels
+    None,
+    /// Ordered dithering using Bayer matrix
+    Bayer(base::Bayer),
+    /// Floyd-Steinberg error diffusion
+    FloydSteinberg,
+}
+
+impl Dither {
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "None" => Dither::None,
+            "Bayer2" => Dither::Bayer(base::Bayer::Bayer2),
+            "Bayer4" => Dither::Bayer(base::Bayer::Bayer4),
+            "Bayer8" => Dither::Bayer(base::Bayer::Bayer8),
+            "FloydSteinberg" => Dither::FloydSteinberg,
+            _ => return None,
+        })
+    }
+}
+
+fn add_error(value: u8, error: i32) -> u8 {
+    (value as i32 + error / 16).clamp(0, 255) as u8
+}
+
+/// Reduce color to nearest palette color using threshold as fraction of 256
+///
+/// The threshold offsets color by up to half of average distance between palette colors.
+pub fn reduce_to_palette(palette: &[RGBA], color: RGBA, threshold: u8) -> RGBA {
+    let spread = 256 / (palette.len() as f32).cbrt().max(1.0) as i32;
+    let offset = (threshold as i32 - 128) * spread / 256;
+    let adjust = |value: u8| (value as i32 + offset).clamp(0, 255) as u8;
+    let wanted = RGBA::new(adjust(color.r), adjust(color.g), adjust(color.b), color.a);
+
+    palette.iter().cloned().min_by_key(|entry| entry.distance(&wanted)).unwrap_or(color)
+}
+
+/// Reduce colors to format using dithering and store it to buffer
+///
+/// The reduce function maps color to format using threshold as fraction of 256.
+pub fn dither_pixels<Fmt, Reduce>(format: &Fmt, size: Size, colors: &[RGBA], dither: Dither, reduce: Reduce, buffer: &mut [u8])
+where
+    Fmt: ColorSet,
+    Fmt::ColorType: Into<RGBA> + Clone,
+    Reduce: Fn(RGBA, u8) -> Fmt::ColorType,
+{
+    let width = size.w as usize;
+
+    // errors of current and next rows in sixteenths with one extra cell at each side
+    let mut errors = vec![[0i32; 3]; width + 2];
+    let mut next_errors = vec![[0i32; 3]; width + 2];
+
+    for (index, color) in colors.iter().enumerate() {
+        let (x, y) = (index % width, index / width);
+
+        let reduced = match dither {
+            Dither::None => reduce(*color, 128),
+            Dither::Bayer(matrix) => reduce(*color, matrix.threshold(Point::new(x as i32, y as i32))),
+            Dither::FloydSteinberg => {
+                let [r, g, b] = errors[x + 1];
+                let wanted = RGBA::new(add_error(color.r, r), add_error(color.g, g), add_error(color.b, b), color.a);
+                let reduced = reduce(wanted, 128);
+                let got: RGBA = reduced.clone().into();
+
+                let error = [
+                    wanted.r as i32 - got.r as i32,
+                    wanted.g as i32 - got.g as i32,
+                    wanted.b as i32 - got.b as i32,
+                ];
+
+                for channel in 0..3 {
+                    errors[x + 2][channel] += error[channel] * 7;
+                    next_errors[x][channel] += error[channel] * 3;
+                    next_errors[x + 1][channel] += error[channel] * 5;
+                    next_errors[x + 2][channel] += error[channel];
+                }
+
+                if x + 1 == width {
+                    core::mem::swap(&mut errors, &mut next_errors);
+                    next_errors.iter_mut().for_each(|error| *error = [0; 3]);
+                }
+
+                reduced
+            }
+        };
+
+        format.set_color(buffer, index, reduced);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use base::{format, ColorDepth, ColorGet, ConstDefault, GS};
+
+    #[test]
+    fn names() {
+        assert_eq!(Dither::from_name("None"), Some(Dither::None));
+        assert_eq!(Dither::from_name("Bayer8"), Some(Dither::Bayer(base::Bayer::Bayer8)));
+        assert_eq!(Dither::from_name("FloydSteinberg"), Some(Dither::FloydSteinberg));
+        assert_eq!(Dither::from_name("Random"), None);
+    }
+
+    #[test]
+    fn floyd_steinberg() {
+        let size = Size { w: 8, h: 8 };
+        let colors = vec![RGBA::new(64, 64, 64, 255); 64];
+        let fmt = <format::BW1>::DEFAULT;
+        let mut buffer = [0u8; 8];
+
+        dither_pixels(&fmt, size, &colors, Dither::None, <format::BW1>::reduce, &mut buffer);
+
+        assert_eq!(buffer, [0; 8]);
+
+        dither_pixels(&fmt, size, &colors, Dither::FloydSteinberg, <format::BW1>::reduce, &mut buffer);
+
+        let white = (0..64).filter(|index| fmt.get_color(&buffer, *index) == true.into()).count();
+
+        // about quarter of pixels, excluding error lost at edges
+        assert!((14..=16).contains(&white), "{}", white);
+    }
+
+    #[test]
+    fn bayer() {
+        let size = Size { w: 4, h: 4 };
+        let colors = vec![RGBA::new(128, 128, 128, 255); 16];
+        let fmt = <format::GS1>::DEFAULT;
+        let mut buffer = [0u8; 2];
+
+        dither_pixels(&fmt, size, &colors, Dither::Bayer(base::Bayer::Bayer4), <format::GS1>::reduce, &mut buffer);
+
+        let white = (0..16).filter(|index| fmt.get_color(&buffer, *index) == GS::new(255)).count();
+
+        assert_eq!(white, 8);
+    }
+
+    #[test]
+    fn palette() {
+        let palette = [RGBA::new(0, 0, 0, 255), RGBA::new(255, 0, 0, 255), RGBA::new(255, 255, 255, 255)];
+
+        assert_eq!(reduce_to_palette(&palette, RGBA::new(200, 20, 10, 255), 128), palette[1]);
+        assert_eq!(reduce_to_palette(&palette, RGBA::new(100, 100, 100, 255), 0), palette[0]);
+        assert_eq!(reduce_to_palette(&palette, RGBA::new(160, 160, 160, 255), 255), palette[2]);
+
+        let size = Size { w: 4, h: 4 };
+        let colors = vec![RGBA::new(128, 128, 128, 255); 16];
+        let palette = [RGBA::new(0, 0, 0, 255), RGBA::new(255, 255, 255, 255)];
+        let fmt = <format::IDX1<_>>::new((format::RGBA8888, palette.iter().flat_map(|c| [c.r, c.g, c.b, c.a]).collect::<Vec<u8>>()));
+        let mut buffer = [0u8; 2];
+
+        dither_pixels(&fmt, size, &colors, Dither::Bayer(base::Bayer::Bayer4), |color, threshold| reduce_to_palette(&palette, color, threshold), &mut buffer);
+
+        let white = (0..16).filter(|index| fmt.get_color(&buffer, *index) == palette[1]).count();
+
+        assert_eq!(white, 8);
+    }
+}
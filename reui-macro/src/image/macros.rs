@@ -3,7 +3,7 @@ use proc_macro_error::abort;
 use quote::{quote, format_ident};
 use reui_core as base;
 
-use super::{ImageAttr, ImageItem, ImageData, ColorFmt, ColorData};
+use super::{ImageAttr, ImageItem, ImageData, ColorFmt, ColorData, Dither, dither_pixels, reduce_to_palette, median_cut};
 use crate::utils::{get_crate_name, get_source_path};
 
 pub struct ImageMacro {
@@ -36,6 +36,31 @@ impl ImageMacro {
 
         let image_size = image_data.size;
 
+        let target_format = self.attr.format.as_ref().map(|name| match ColorFmt::from_name(&name.to_string()) {
+            // build optimized palette for image
+            Some(ColorFmt::IDX(bits, _)) => ColorFmt::IDX(bits, median_cut(&image_data.colors(), 1 << bits)),
+            Some(format) => format,
+            None => abort!(name, "Unsupported target color format {}", name),
+        });
+
+        let dither = match &self.attr.dither {
+            Some(name) if target_format.is_none() => abort!(name, "Dithering requires target color format"),
+            Some(name) => match Dither::from_name(&name.to_string()) {
+                Some(dither) => dither,
+                None => abort!(name, "Unknown dithering method {}", name),
+            },
+            None => Dither::None,
+        };
+
+        let order = match &self.attr.order {
+            Some(name) => match name.to_string().as_str() {
+                "LSB" => None,
+                "MSB" => Some(quote! { #core_crate::MSB }),
+                _ => abort!(name, "Unknown bit order {}, expected LSB or MSB", name),
+            },
+            None => None,
+        };
+
         let size_width = Literal::u32_unsuffixed(image_size.w);
         let size_height = Literal::u32_unsuffixed(image_size.h);
 
@@ -43,19 +68,25 @@ impl ImageMacro {
             ($type: ident, $fmt: literal, $($bits: expr),+) => {
                 {
                     let fmt = format_ident!($fmt, $($bits),+);
-                    ($($bits +)+ 0,
-                     quote! { #core_crate::format::#fmt },
-                     quote! { <#core_crate::format::#fmt as #core_crate::ConstDefault>::DEFAULT })
+                    let bits = $($bits +)+ 0;
+                    let fmt_type = match &order {
+                        Some(order) if bits < 8 => quote! { #core_crate::format::#fmt<#order> },
+                        _ => quote! { #core_crate::format::#fmt },
+                    };
+                    (bits,
+                     fmt_type.clone(),
+                     quote! { <#fmt_type as #core_crate::ConstDefault>::DEFAULT })
                 }
             };
         }
 
-        let (fmt_bits, fmt_type, fmt_value) = match image_data.format {
+        let (fmt_bits, fmt_type, fmt_value) = match target_format.as_ref().unwrap_or(&image_data.format) {
+            ColorFmt::BW(bits) => pixel_format!(BW, "BW{}", bits),
             ColorFmt::GS(bits) => pixel_format!(GS, "GS{}", bits),
             ColorFmt::GSA(bits, abits) => pixel_format!(GSA, "GSA{}{}", bits, abits),
             ColorFmt::RGB(rbits, gbits, bbits) => pixel_format!(RGB, "RGB{}{}{}", rbits, gbits, bbits),
             ColorFmt::RGBA(rbits, gbits, bbits, abits) => pixel_format!(RGBA, "RGBA{}{}{}{}", rbits, gbits, bbits, abits),
-            ColorFmt::IDX(bits, ref palette) => {
+            ColorFmt::IDX(bits, palette) => {
                 let palette_data = palette.iter().map(|c| {
                     let r = Literal::u8_unsuffixed(c.r);
                     let g = Literal::u8_unsuffixed(c.g);
@@ -65,8 +96,12 @@ impl ImageMacro {
                 });
 
                 let fmt = format_ident!("IDX{}", bits);
-                (bits,
-                 quote! { #core_crate::format::#fmt<(&#core_crate::format::RGBA8888, &[u8])> },
+                let fmt_type = match &order {
+                    Some(order) if *bits < 8 => quote! { #core_crate::format::#fmt<(&#core_crate::format::RGBA8888, &[u8]), #order> },
+                    _ => quote! { #core_crate::format::#fmt<(&#core_crate::format::RGBA8888, &[u8])> },
+                };
+                (*bits,
+                 fmt_type,
                  quote! { #core_crate::format::#fmt::new((
                      &#core_crate::format::RGBA8888,
                      &[#(#palette_data),*]
@@ -74,16 +109,20 @@ impl ImageMacro {
             },
         };
 
+        if let (Some(name), true) = (&self.attr.order, order.is_some() && fmt_bits >= 8) {
+            abort!(name, "Bit order requires color format with less than 8 bits per color");
+        }
+
         let pixel_bytes = ((image_size.w * image_size.h * fmt_bits as u32 + 7) / 8) as usize;
         let mut pixel_data = Vec::with_capacity(pixel_bytes);
         pixel_data.resize(pixel_bytes, 0);
 
-        use base::{ConstDefault, ColorBufMut};
+        use base::{ConstDefault, ColorBufMut, ColorDepth};
 
         macro_rules! convert_pixels {
             ($fmt: ident, $src: ident, $dst: ident) => {
                 {
-                    let mut dest = (&base::format::$fmt::DEFAULT, $dst.as_mut() as &mut [u8]);
+                    let mut dest = (&<base::format::$fmt>::DEFAULT, $dst.as_mut() as &mut [u8]);
                     for (index, color) in $src.iter().enumerate() {
                         dest.set(index, *color);
                     }
@@ -91,57 +130,103 @@ impl ImageMacro {
             };
         }
 
-        match &image_data.pixels {
-            ColorData::GS(pixels) => {
-                match &image_data.format {
-                    ColorFmt::GS(1) => convert_pixels!(GS1, pixels, pixel_data),
-                    ColorFmt::GS(2) => convert_pixels!(GS2, pixels, pixel_data),
-                    ColorFmt::GS(4) => convert_pixels!(GS4, pixels, pixel_data),
-                    ColorFmt::GS(8) => convert_pixels!(GS8, pixels, pixel_data),
-                    ColorFmt::GS(bits) => abort!(self.item.ident, "Unsupported color format GS{}", bits),
-                    _ => unreachable!(),
-                }
-            },
-            ColorData::GSA(pixels) => {
-                match &image_data.format {
-                    ColorFmt::GSA(3, 1) => convert_pixels!(GSA31, pixels, pixel_data),
-                    ColorFmt::GSA(4, 4) => convert_pixels!(GSA44, pixels, pixel_data),
-                    ColorFmt::GSA(7, 1) => convert_pixels!(GSA71, pixels, pixel_data),
-                    ColorFmt::GSA(8, 8) => convert_pixels!(GSA88, pixels, pixel_data),
-                    ColorFmt::GSA(bits, abits) => abort!(self.item.ident, "Unsupported color format GSA{}{}", bits, abits),
-                    _ => unreachable!(),
-                }
-            },
-            ColorData::RGB(pixels) => {
-                match &image_data.format {
-                    ColorFmt::RGB(3, 3, 2) => convert_pixels!(RGB332, pixels, pixel_data),
-                    ColorFmt::RGB(4, 4, 4) => convert_pixels!(RGB444, pixels, pixel_data),
-                    ColorFmt::RGB(5, 6, 5) => convert_pixels!(RGB565, pixels, pixel_data),
-                    ColorFmt::RGB(8, 8, 8) => convert_pixels!(RGB888, pixels, pixel_data),
-                    ColorFmt::RGB(rbits, gbits, bbits) => abort!(self.item.ident, "Unsupported color format RGB{}{}{}", rbits, gbits, bbits),
-                    _ => unreachable!(),
-                }
-            },
-            ColorData::RGBA(pixels) => {
-                match &image_data.format {
-                    ColorFmt::RGBA(4, 4, 4, 4) => convert_pixels!(RGBA4444, pixels, pixel_data),
-                    ColorFmt::RGBA(5, 5, 5, 1) => convert_pixels!(RGBA5551, pixels, pixel_data),
-                    ColorFmt::RGBA(8, 8, 8, 8) => convert_pixels!(RGBA8888, pixels, pixel_data),
-                    ColorFmt::RGBA(rbits, gbits, bbits, abits) => abort!(self.item.ident, "Unsupported color format RGBA{}{}{}{}", rbits, gbits, bbits, abits),
-                    _ => unreachable!(),
-                }
-            },
-            ColorData::IDX(indexes) => {
-                match &image_data.format {
-                    ColorFmt::IDX(1, _) => convert_pixels!(RAW1, indexes, pixel_data),
-                    ColorFmt::IDX(2, _) => convert_pixels!(RAW2, indexes, pixel_data),
-                    ColorFmt::IDX(4, _) => convert_pixels!(RAW4, indexes, pixel_data),
-                    ColorFmt::IDX(8, _) => convert_pixels!(RAW8, indexes, pixel_data),
-                    ColorFmt::IDX(bits, _) => abort!(self.item.ident, "Unsupported color format IDX{}", bits),
-                    _ => unreachable!(),
+        macro_rules! dither_pixels {
+            ($fmt: ident) => {
+                dither_pixels(&<base::format::$fmt>::DEFAULT, image_size, &image_data.colors(), dither, <base::format::$fmt>::reduce, &mut pixel_data)
+            };
+            ($fmt: ident, $palette: ident) => {
+                {
+                    let colors = $palette.iter().flat_map(|c| [c.r, c.g, c.b, c.a]).collect::<Vec<u8>>();
+                    let reduce = |color, threshold| reduce_to_palette($palette, color, threshold);
+                    dither_pixels(&<base::format::$fmt<_>>::new((base::format::RGBA8888, colors)), image_size, &image_data.colors(), dither, reduce, &mut pixel_data)
                 }
-            },
-        };
+            };
+        }
+
+        if let Some(format) = &target_format {
+            match format {
+                ColorFmt::BW(1) => dither_pixels!(BW1),
+                ColorFmt::GS(1) => dither_pixels!(GS1),
+                ColorFmt::GS(2) => dither_pixels!(GS2),
+                ColorFmt::GS(4) => dither_pixels!(GS4),
+                ColorFmt::GS(8) => dither_pixels!(GS8),
+                ColorFmt::GSA(1, 1) => dither_pixels!(GSA11),
+                ColorFmt::GSA(2, 2) => dither_pixels!(GSA22),
+                ColorFmt::GSA(3, 1) => dither_pixels!(GSA31),
+                ColorFmt::GSA(4, 4) => dither_pixels!(GSA44),
+                ColorFmt::GSA(7, 1) => dither_pixels!(GSA71),
+                ColorFmt::GSA(8, 8) => dither_pixels!(GSA88),
+                ColorFmt::RGB(3, 3, 2) => dither_pixels!(RGB332),
+                ColorFmt::RGB(4, 4, 4) => dither_pixels!(RGB444),
+                ColorFmt::RGB(5, 6, 5) => dither_pixels!(RGB565),
+                ColorFmt::RGB(8, 8, 8) => dither_pixels!(RGB888),
+                ColorFmt::RGBA(4, 4, 4, 4) => dither_pixels!(RGBA4444),
+                ColorFmt::RGBA(5, 5, 5, 1) => dither_pixels!(RGBA5551),
+                ColorFmt::RGBA(8, 8, 8, 8) => dither_pixels!(RGBA8888),
+                ColorFmt::IDX(1, palette) => dither_pixels!(IDX1, palette),
+                ColorFmt::IDX(2, palette) => dither_pixels!(IDX2, palette),
+                ColorFmt::IDX(4, palette) => dither_pixels!(IDX4, palette),
+                ColorFmt::IDX(8, palette) => dither_pixels!(IDX8, palette),
+                _ => unreachable!(),
+            }
+        } else {
+            match &image_data.pixels {
+                ColorData::GS(pixels) => {
+                    match &image_data.format {
+                        ColorFmt::GS(1) => convert_pixels!(GS1, pixels, pixel_data),
+                        ColorFmt::GS(2) => convert_pixels!(GS2, pixels, pixel_data),
+                        ColorFmt::GS(4) => convert_pixels!(GS4, pixels, pixel_data),
+                        ColorFmt::GS(8) => convert_pixels!(GS8, pixels, pixel_data),
+                        ColorFmt::GS(bits) => abort!(self.item.ident, "Unsupported color format GS{}", bits),
+                        _ => unreachable!(),
+                    }
+                },
+                ColorData::GSA(pixels) => {
+                    match &image_data.format {
+                        ColorFmt::GSA(3, 1) => convert_pixels!(GSA31, pixels, pixel_data),
+                        ColorFmt::GSA(4, 4) => convert_pixels!(GSA44, pixels, pixel_data),
+                        ColorFmt::GSA(7, 1) => convert_pixels!(GSA71, pixels, pixel_data),
+                        ColorFmt::GSA(8, 8) => convert_pixels!(GSA88, pixels, pixel_data),
+                        ColorFmt::GSA(bits, abits) => abort!(self.item.ident, "Unsupported color format GSA{}{}", bits, abits),
+                        _ => unreachable!(),
+                    }
+                },
+                ColorData::RGB(pixels) => {
+                    match &image_data.format {
+                        ColorFmt::RGB(3, 3, 2) => convert_pixels!(RGB332, pixels, pixel_data),
+                        ColorFmt::RGB(4, 4, 4) => convert_pixels!(RGB444, pixels, pixel_data),
+                        ColorFmt::RGB(5, 6, 5) => convert_pixels!(RGB565, pixels, pixel_data),
+                        ColorFmt::RGB(8, 8, 8) => convert_pixels!(RGB888, pixels, pixel_data),
+                        ColorFmt::RGB(rbits, gbits, bbits) => abort!(self.item.ident, "Unsupported color format RGB{}{}{}", rbits, gbits, bbits),
+                        _ => unreachable!(),
+                    }
+                },
+                ColorData::RGBA(pixels) => {
+                    match &image_data.format {
+                        ColorFmt::RGBA(4, 4, 4, 4) => convert_pixels!(RGBA4444, pixels, pixel_data),
+                        ColorFmt::RGBA(5, 5, 5, 1) => convert_pixels!(RGBA5551, pixels, pixel_data),
+                        ColorFmt::RGBA(8, 8, 8, 8) => convert_pixels!(RGBA8888, pixels, pixel_data),
+                        ColorFmt::RGBA(rbits, gbits, bbits, abits) => abort!(self.item.ident, "Unsupported color format RGBA{}{}{}{}", rbits, gbits, bbits, abits),
+                        _ => unreachable!(),
+                    }
+                },
+                ColorData::IDX(indexes) => {
+                    match &image_data.format {
+                        ColorFmt::IDX(1, _) => convert_pixels!(RAW1, indexes, pixel_data),
+                        ColorFmt::IDX(2, _) => convert_pixels!(RAW2, indexes, pixel_data),
+                        ColorFmt::IDX(4, _) => convert_pixels!(RAW4, indexes, pixel_data),
+                        ColorFmt::IDX(8, _) => convert_pixels!(RAW8, indexes, pixel_data),
+                        ColorFmt::IDX(bits, _) => abort!(self.item.ident, "Unsupported color format IDX{}", bits),
+                        _ => unreachable!(),
+                    }
+                },
+            }
+        }
+
+        // pixels are packed using LSB-first formats
+        if order.is_some() {
+            reverse_bit_order(&mut pixel_data, fmt_bits);
+        }
 
         let pixel_data = pixel_data.iter().map(|byte| {
             let byte = Literal::u8_unsuffixed(*byte);
@@ -158,3 +243,30 @@ impl ImageMacro {
         }
     }
 }
+
+/// Reverse order of colors in each byte to convert LSB-first data to MSB-first
+fn reverse_bit_order(data: &mut [u8], bits: u8) {
+    let mask = !0u8 >> (8 - bits);
+
+    for byte in data {
+        let value = *byte;
+        *byte = (0..8).step_by(bits as usize)
+            .fold(0, |acc, shift| (acc << bits) | ((value >> shift) & mask));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bit_order() {
+        let mut data = [0b0000_0001, 0b1100_1010, 0b1110_0100];
+
+        reverse_bit_order(&mut data[..1], 1);
+        reverse_bit_order(&mut data[1..2], 2);
+        reverse_bit_order(&mut data[2..], 4);
+
+        assert_eq!(data, [0b1000_0000, 0b1010_0011, 0b0100_1110]);
+    }
+}
@@ -0,0 +1,90 @@
+use reui_core as base;
+use base::RGBA;
+
+fn channels(color: &RGBA) -> [u8; 4] {
+    [color.r, color.g, color.b, color.a]
+}
+
+/// Get channel with largest range of values and its range
+fn widest_channel(colors: &[RGBA]) -> (usize, u8) {
+    (0..4).map(|channel| {
+        let values = colors.iter().map(|color| channels(color)[channel]);
+        let range = values.clone().max().unwrap_or(0) - values.min().unwrap_or(0);
+        (channel, range)
+    }).max_by_key(|(_, range)| *range).unwrap()
+}
+
+fn average(colors: &[RGBA]) -> RGBA {
+    let mut sum = [0usize; 4];
+
+    for color in colors {
+        for (sum, value) in sum.iter_mut().zip(channels(color).iter()) {
+            *sum += *value as usize;
+        }
+    }
+
+    let [r, g, b, a] = sum.map(|sum| ((sum + colors.len() / 2) / colors.len()) as u8);
+    RGBA::new(r, g, b, a)
+}
+
+/// Build palette of specified size using median cut
+///
+/// When image has no more unique colors than palette size, the colors are used as is.
+/// Otherwise the boxes with widest channel range are split at median until palette is filled.
+pub fn median_cut(colors: &[RGBA], count: usize) -> Vec<RGBA> {
+    let mut unique = colors.to_vec();
+    unique.sort_by_key(channels);
+    unique.dedup();
+
+    if unique.len() <= count {
+        return unique;
+    }
+
+    let mut boxes = vec![colors.to_vec()];
+
+    while boxes.len() < count {
+        let (index, channel, range) = boxes.iter().enumerate().map(|(index, colors)| {
+            let (channel, range) = widest_channel(colors);
+            (index, channel, range)
+        }).max_by_key(|(_, _, range)| *range).unwrap();
+
+        if range == 0 {
+            break;
+        }
+
+        let mut colors = boxes.swap_remove(index);
+        colors.sort_by_key(|color| channels(color)[channel]);
+
+        let upper = colors.split_off(colors.len() / 2);
+        boxes.push(colors);
+        boxes.push(upper);
+    }
+
+    boxes.iter().map(|colors| average(colors)).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unique() {
+        let colors = [RGBA::new(255, 0, 0, 255), RGBA::new(0, 0, 0, 255), RGBA::new(255, 0, 0, 255)];
+        let palette = median_cut(&colors, 4);
+
+        assert_eq!(palette, [RGBA::new(0, 0, 0, 255), RGBA::new(255, 0, 0, 255)]);
+    }
+
+    #[test]
+    fn clusters() {
+        let colors = [
+            RGBA::new(10, 200, 0, 255), RGBA::new(12, 210, 2, 255), RGBA::new(14, 220, 4, 255),
+            RGBA::new(200, 0, 100, 255), RGBA::new(210, 4, 110, 255), RGBA::new(220, 8, 120, 255),
+        ];
+        let mut palette = median_cut(&colors, 2);
+        palette.sort_by_key(channels);
+
+        assert_eq!(palette, [RGBA::new(12, 210, 2, 255), RGBA::new(210, 4, 110, 255)]);
+        assert_eq!(median_cut(&colors, 4).len(), 4);
+    }
+}
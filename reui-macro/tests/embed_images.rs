@@ -1,4 +1,4 @@
-use reui::{embed, Size, Point, GS, GSA, RGB, RGBA};
+use reui::{embed, Size, Point, BW, GS, GSA, RGB, RGBA};
 
 #[embed("../images/gs_2x2.png")]
 pub static GS_2X2: Image = ();
@@ -18,6 +18,27 @@ pub static IDX_2X2: Image = ();
 #[embed("../images/idxa_2x2.png")]
 pub static IDXA_2X2: Image = ();
 
+#[embed("../images/gs_2x2.png", format = BW1)]
+pub static GS_2X2_BW1: Image = ();
+
+#[embed("../images/gs_2x2.png", format = BW1, order = MSB)]
+pub static GS_2X2_BW1_MSB: Image = ();
+
+#[embed("../images/rgb_2x2.png", format = IDX4, order = MSB)]
+pub static RGB_2X2_IDX4_MSB: Image = ();
+
+#[embed("../images/rgb_2x2.png", format = RGB332, dither = FloydSteinberg)]
+pub static RGB_2X2_RGB332: Image = ();
+
+#[embed("../images/rgba_2x2.png", format = GSA44, dither = Bayer2)]
+pub static RGBA_2X2_GSA44: Image = ();
+
+#[embed("../images/rgb_2x2.png", format = IDX4)]
+pub static RGB_2X2_IDX4: Image = ();
+
+#[embed("../images/rgb_2x2.png", format = IDX1, dither = FloydSteinberg)]
+pub static RGB_2X2_IDX1: Image = ();
+
 #[test]
 fn test_gs_2x2() {
     assert_eq!(GS_2X2.size(), Size::new(2, 2));
@@ -72,3 +93,63 @@ fn test_idxa_2x2() {
     assert_eq!(IDXA_2X2.get(Point::new(0, 1)), RGBA::new(0, 0, 255, 255));
     assert_eq!(IDXA_2X2.get(Point::new(1, 1)), RGBA::new(255, 255, 255, 255));
 }
+
+#[test]
+fn test_gs_2x2_bw1() {
+    assert_eq!(GS_2X2_BW1.size(), Size::new(2, 2));
+    assert_eq!(GS_2X2_BW1.get(Point::new(0, 0)), BW::from(false));
+    assert_eq!(GS_2X2_BW1.get(Point::new(1, 0)), BW::from(true));
+    assert_eq!(GS_2X2_BW1.get(Point::new(0, 1)), BW::from(false));
+    assert_eq!(GS_2X2_BW1.get(Point::new(1, 1)), BW::from(true));
+}
+
+#[test]
+fn test_gs_2x2_bw1_msb() {
+    assert_eq!(GS_2X2_BW1_MSB.size(), Size::new(2, 2));
+    assert_eq!(GS_2X2_BW1_MSB.get(Point::new(0, 0)), BW::from(false));
+    assert_eq!(GS_2X2_BW1_MSB.get(Point::new(1, 0)), BW::from(true));
+    assert_eq!(GS_2X2_BW1_MSB.get(Point::new(0, 1)), BW::from(false));
+    assert_eq!(GS_2X2_BW1_MSB.get(Point::new(1, 1)), BW::from(true));
+    assert_eq!(GS_2X2_BW1_MSB.clone().unwrap().1, &[0b0101_0000]);
+}
+
+#[test]
+fn test_rgb_2x2_idx4_msb() {
+    assert_eq!(RGB_2X2_IDX4_MSB.size(), Size::new(2, 2));
+    assert_eq!(RGB_2X2_IDX4_MSB.get(Point::new(0, 0)), RGBA::new(0, 0, 0, 255));
+    assert_eq!(RGB_2X2_IDX4_MSB.get(Point::new(1, 0)), RGBA::new(255, 0, 0, 255));
+    assert_eq!(RGB_2X2_IDX4_MSB.get(Point::new(0, 1)), RGBA::new(0, 0, 255, 255));
+    assert_eq!(RGB_2X2_IDX4_MSB.get(Point::new(1, 1)), RGBA::new(255, 255, 255, 255));
+}
+
+#[test]
+fn test_rgb_2x2_rgb332() {
+    assert_eq!(RGB_2X2_RGB332.size(), Size::new(2, 2));
+    assert_eq!(RGB_2X2_RGB332.get(Point::new(0, 0)), RGB::new(0, 0, 0));
+    assert_eq!(RGB_2X2_RGB332.get(Point::new(1, 0)), RGB::new(252, 0, 0));
+    assert_eq!(RGB_2X2_RGB332.get(Point::new(0, 1)), RGB::new(0, 0, 255));
+    assert_eq!(RGB_2X2_RGB332.get(Point::new(1, 1)), RGB::new(252, 252, 255));
+}
+
+#[test]
+fn test_rgba_2x2_gsa44() {
+    assert_eq!(RGBA_2X2_GSA44.size(), Size::new(2, 2));
+    assert_eq!(RGBA_2X2_GSA44.get(Point::new(0, 0)), GSA::new(0, 255));
+    assert_eq!(RGBA_2X2_GSA44.get(Point::new(1, 1)), GSA::new(255, 255));
+    assert_eq!(RGBA_2X2_GSA44.get(Point::new(0, 1)).a, 136);
+}
+
+#[test]
+fn test_rgb_2x2_idx4() {
+    assert_eq!(RGB_2X2_IDX4.size(), Size::new(2, 2));
+    assert_eq!(RGB_2X2_IDX4.get(Point::new(0, 0)), RGBA::new(0, 0, 0, 255));
+    assert_eq!(RGB_2X2_IDX4.get(Point::new(1, 0)), RGBA::new(255, 0, 0, 255));
+    assert_eq!(RGB_2X2_IDX4.get(Point::new(0, 1)), RGBA::new(0, 0, 255, 255));
+    assert_eq!(RGB_2X2_IDX4.get(Point::new(1, 1)), RGBA::new(255, 255, 255, 255));
+}
+
+#[test]
+fn test_rgb_2x2_idx1() {
+    assert_eq!(RGB_2X2_IDX1.size(), Size::new(2, 2));
+    assert_ne!(RGB_2X2_IDX1.get(Point::new(0, 0)), RGB_2X2_IDX1.get(Point::new(1, 1)));
+}